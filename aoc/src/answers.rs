@@ -0,0 +1,433 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use input::{self, Input, ParseError};
+
+use report::quote;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+pub fn input_hash(contents: &str) -> String {
+    let hash = contents.bytes().fold(FNV_OFFSET_BASIS, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+    });
+
+    format!("{:016x}", hash)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: Vec<Answer>,
+}
+
+struct Entry {
+    line: usize,
+    header: String,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    answer: Option<String>,
+}
+
+impl Entry {
+    fn new(line: usize, header: &str) -> Self {
+        Entry {
+            line,
+            header: header.to_owned(),
+            day: None,
+            part: None,
+            input: None,
+            answer: None,
+        }
+    }
+
+    fn finish(self) -> Result<Answer, ParseError> {
+        let missing =
+            |key: &str| ParseError::at_end(&self.header, &format!("`{}` in this entry", key));
+
+        Ok(Answer {
+            day: self.day.ok_or_else(|| missing("day"))?,
+            part: self.part.ok_or_else(|| missing("part"))?,
+            input: self.input.clone().ok_or_else(|| missing("input"))?,
+            answer: self.answer.clone().ok_or_else(|| missing("answer"))?,
+        })
+    }
+}
+
+struct Value {
+    text: String,
+    quoted: bool,
+    span: Range<usize>,
+}
+
+impl Value {
+    fn number(self, line: &str) -> Result<u32, ParseError> {
+        match self.text.parse() {
+            Ok(n) if !self.quoted => Ok(n),
+            _ => Err(ParseError::new(line, self.span, "an integer")),
+        }
+    }
+
+    fn string(self, line: &str) -> Result<String, ParseError> {
+        if self.quoted {
+            Ok(self.text)
+        } else {
+            Err(ParseError::new(line, self.span, "a quoted string"))
+        }
+    }
+}
+
+fn parse_unicode(line: &str, start: usize) -> Result<char, ParseError> {
+    let digits = &line[start + 2..];
+    let end = digits
+        .char_indices()
+        .nth(4)
+        .map_or(line.len(), |(i, _)| start + 2 + i);
+    let hex = &line[start + 2..end];
+
+    if hex.len() == 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        if let Some(c) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+            return Ok(c);
+        }
+    }
+
+    Err(ParseError::new(line, start..end, "a `\\uXXXX` escape"))
+}
+
+fn parse_string(line: &str, start: usize) -> Result<Value, ParseError> {
+    let mut text = String::new();
+    let mut chars = line[start + 1..]
+        .char_indices()
+        .map(|(i, c)| (start + 1 + i, c));
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                return Ok(Value {
+                    text,
+                    quoted: true,
+                    span: start..(i + 1),
+                })
+            }
+            '\\' => match chars.next() {
+                Some((_, '"')) => text.push('"'),
+                Some((_, '\\')) => text.push('\\'),
+                Some((_, 'n')) => text.push('\n'),
+                Some((_, 'r')) => text.push('\r'),
+                Some((_, 't')) => text.push('\t'),
+                Some((_, 'u')) => {
+                    text.push(parse_unicode(line, i)?);
+                    chars.nth(3);
+                }
+                Some((j, c)) => {
+                    let escape = i..(j + c.len_utf8());
+                    return Err(ParseError::new(line, escape, "an escape sequence"));
+                }
+                None => break,
+            },
+            c => text.push(c),
+        }
+    }
+
+    Err(ParseError::at_end(line, "a closing `\"`"))
+}
+
+fn parse_pair(line: &str) -> Result<(&str, Value), ParseError> {
+    let equals = line
+        .find('=')
+        .ok_or_else(|| ParseError::at_end(line, "`=` followed by a value"))?;
+
+    let key = line[..equals].trim();
+    let rest = &line[equals + 1..];
+    let start = line.len() - rest.trim_start().len();
+
+    let value = if line[start..].starts_with('"') {
+        parse_string(line, start)?
+    } else {
+        let end = line[start..]
+            .find(|c: char| c.is_whitespace() || c == '#')
+            .map_or(line.len(), |i| start + i);
+
+        if start == end {
+            return Err(ParseError::at_end(line, "a value"));
+        }
+
+        Value {
+            text: line[start..end].to_owned(),
+            quoted: false,
+            span: start..end,
+        }
+    };
+
+    let rest = line[value.span.end..].trim_start();
+
+    if !rest.is_empty() && !rest.starts_with('#') {
+        let offset = line.len() - rest.len();
+        return Err(ParseError::new(line, offset..line.len(), "end of line"));
+    }
+
+    Ok((key, value))
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Answers {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, input::Error> {
+        match Input::from_path(path) {
+            Ok(input) => Answers::parse(&input),
+            Err(input::Error::Io { ref error, .. }) if error.kind() == io::ErrorKind::NotFound => {
+                Ok(Answers::new())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(input: &Input) -> Result<Self, input::Error> {
+        let mut answers = Answers::new();
+        let mut entry: Option<Entry> = None;
+
+        for (number, l) in input.lines() {
+            let trimmed = l.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if trimmed == "[[answer]]" {
+                if let Some(e) = entry.take() {
+                    let line = e.line;
+                    answers.insert(e.finish().map_err(|e| input.syntax(line, e))?);
+                }
+
+                entry = Some(Entry::new(number, trimmed));
+                continue;
+            }
+
+            let current = match entry.as_mut() {
+                Some(e) => e,
+                None => {
+                    return Err(input.syntax(
+                        number,
+                        ParseError::new(trimmed, 0..trimmed.len(), "`[[answer]]`"),
+                    ))
+                }
+            };
+
+            let (key, value) = parse_pair(trimmed).map_err(|e| input.syntax(number, e))?;
+
+            let result = match key {
+                "day" => value.number(trimmed).map(|v| current.day = Some(v)),
+                "part" => value.number(trimmed).map(|v| current.part = Some(v)),
+                "input" => value.string(trimmed).map(|v| current.input = Some(v)),
+                "answer" => value.string(trimmed).map(|v| current.answer = Some(v)),
+                _ => Err(ParseError::new(
+                    trimmed,
+                    0..key.len(),
+                    "`day`, `part`, `input` or `answer`",
+                )),
+            };
+
+            result.map_err(|e| input.syntax(number, e))?;
+        }
+
+        if let Some(e) = entry {
+            let line = e.line;
+            answers.insert(e.finish().map_err(|e| input.syntax(line, e))?);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|a| a.day == day && a.part == part && a.input == input)
+            .map(|a| a.answer.as_str())
+    }
+
+    pub fn is_recorded(&self, day: u32, part: u32) -> bool {
+        self.entries.iter().any(|a| a.day == day && a.part == part)
+    }
+
+    pub fn insert(&mut self, answer: Answer) {
+        self.entries
+            .retain(|a| a.day != answer.day || a.part != answer.part || a.input != answer.input);
+
+        self.entries.push(answer);
+        self.entries
+            .sort_by(|a, b| (a.day, a.part, &a.input).cmp(&(b.day, b.part, &b.input)));
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = File::create(path)?;
+
+        write!(file, "{}", self)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Known answers, keyed by day, part and input hash.")?;
+
+        for a in &self.entries {
+            writeln!(f)?;
+            writeln!(f, "[[answer]]")?;
+            writeln!(f, "day = {}", a.day)?;
+            writeln!(f, "part = {}", a.part)?;
+            writeln!(f, "input = {}", quote(&a.input))?;
+            writeln!(f, "answer = {}", quote(&a.answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(day: u32, part: u32, input: &str, answer: &str) -> Answer {
+        Answer {
+            day,
+            part,
+            input: input.to_owned(),
+            answer: answer.to_owned(),
+        }
+    }
+
+    #[test]
+    fn hash_inputs() {
+        assert_eq!("cbf29ce484222325", input_hash(""));
+        assert_eq!("af63dc4c8601ec8c", input_hash("a"));
+    }
+
+    #[test]
+    fn parse_answers() {
+        let input = Input::embedded(
+            "answers.toml",
+            "# comment\n\n[[answer]]\nday = 7\npart = 1\ninput = \"abc\"\nanswer = \"tknk\" # head\n\n\
+             [[answer]]\nday = 1\npart = 2\ninput = \"def\"\nanswer = \"say \\\"hi\\\"\"\n",
+        );
+
+        let answers = Answers::parse(&input).unwrap();
+
+        assert_eq!(Some("tknk"), answers.get(7, 1, "abc"));
+        assert_eq!(Some("say \"hi\""), answers.get(1, 2, "def"));
+        assert_eq!(None, answers.get(7, 2, "abc"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::new();
+
+        answers.insert(answer(7, 2, "abc", "60"));
+        answers.insert(answer(1, 1, "def", "a \"quoted\" \\ answer"));
+
+        let input = Input::embedded("answers.toml", &answers.to_string());
+
+        assert_eq!(answers, Answers::parse(&input).unwrap());
+    }
+
+    #[test]
+    fn round_trip_control_characters() {
+        let mut answers = Answers::new();
+
+        answers.insert(answer(
+            8,
+            1,
+            "inputs/day08\nfinal.txt",
+            "a\tb\r\n\u{7}\u{1b}[0m é",
+        ));
+
+        let saved = answers.to_string();
+
+        assert!(saved.contains("input = \"inputs/day08\\nfinal.txt\""));
+        assert!(saved.contains("answer = \"a\\tb\\r\\n\\u0007\\u001b[0m é\""));
+        assert_eq!(
+            answers,
+            Answers::parse(&Input::embedded("answers.toml", &saved)).unwrap()
+        );
+    }
+
+    #[test]
+    fn report_invalid_escape() {
+        let input = Input::embedded("answers.toml", "[[answer]]\ninput = \"a\\u12g4\"\n");
+
+        let error = Answers::parse(&input).unwrap_err().to_string();
+
+        assert!(error.starts_with("error: expected a `\\uXXXX` escape, found `\\u12g4`"));
+
+        let input = Input::embedded("answers.toml", "[[answer]]\ninput = \"\\u1é\"\n");
+
+        assert!(Answers::parse(&input).is_err());
+    }
+
+    #[test]
+    fn replace_existing_answer() {
+        let mut answers = Answers::new();
+
+        answers.insert(answer(7, 2, "abc", "60"));
+        answers.insert(answer(7, 2, "abc", "61"));
+
+        assert_eq!(Some("61"), answers.get(7, 2, "abc"));
+        assert_eq!(1, answers.entries.len());
+    }
+
+    #[test]
+    fn report_missing_key() {
+        let input = Input::embedded(
+            "answers.toml",
+            "[[answer]]\nday = 7\npart = 1\ninput = \"abc\"\n",
+        );
+
+        let error = Answers::parse(&input).unwrap_err().to_string();
+
+        assert!(error.starts_with(
+            "error: expected `answer` in this entry, found end of input\n --> answers.toml:1:11"
+        ));
+    }
+
+    #[test]
+    fn report_invalid_value() {
+        let input = Input::embedded("answers.toml", "[[answer]]\nday = seven\n");
+
+        assert_eq!(
+            [
+                "error: expected an integer, found `seven`",
+                " --> answers.toml:2:7",
+                "  |",
+                "2 | day = seven",
+                "  |       ^^^^^ expected an integer",
+            ]
+            .join("\n"),
+            Answers::parse(&input).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn report_unknown_key() {
+        let input = Input::embedded("answers.toml", "[[answer]]\nyear = 2017\n");
+
+        let error = Answers::parse(&input).unwrap_err().to_string();
+
+        assert!(
+            error.starts_with("error: expected `day`, `part`, `input` or `answer`, found `year`")
+        );
+    }
+}
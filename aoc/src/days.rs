@@ -0,0 +1,455 @@
+use std::error::Error;
+
+use day02::Spreadsheet;
+use day07::Tower;
+use day08::Processor;
+use day11::HexCoords;
+use day12::Graph;
+use day13::Firewall;
+use day14::Grid;
+use day15::Judge;
+use day16::Dance;
+use day17::Spinlock;
+use day18::Vm;
+use day19::Map;
+use day20::ParticleSystem;
+use day21::Rulebook;
+use day22::{Cluster, Rules};
+
+use input::{Input, ParseError};
+
+use {Day, Part};
+
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new(
+            1,
+            vec![Part::new(1, day01_part1), Part::new(2, day01_part2)],
+        ),
+        Day::new(
+            2,
+            vec![Part::new(1, day02_part1), Part::new(2, day02_part2)],
+        ),
+        Day::new(
+            3,
+            vec![Part::new(1, day03_part1), Part::new(2, day03_part2)],
+        )
+        .with_default_input("361527"),
+        Day::new(
+            4,
+            vec![Part::new(1, day04_part1), Part::new(2, day04_part2)],
+        ),
+        Day::new(
+            5,
+            vec![Part::new(1, day05_part1), Part::new(2, day05_part2)],
+        ),
+        Day::new(
+            6,
+            vec![Part::new(1, day06_part1), Part::new(2, day06_part2)],
+        ),
+        Day::new(
+            7,
+            vec![Part::new(1, day07_part1), Part::new(2, day07_part2)],
+        ),
+        Day::new(
+            8,
+            vec![Part::new(1, day08_part1), Part::new(2, day08_part2)],
+        ),
+        Day::new(
+            9,
+            vec![Part::new(1, day09_part1), Part::new(2, day09_part2)],
+        ),
+        Day::new(
+            10,
+            vec![Part::new(1, day10_part1), Part::new(2, day10_part2)],
+        ),
+        Day::new(
+            11,
+            vec![Part::new(1, day11_part1), Part::new(2, day11_part2)],
+        ),
+        Day::new(
+            12,
+            vec![Part::new(1, day12_part1), Part::new(2, day12_part2)],
+        ),
+        Day::new(
+            13,
+            vec![Part::new(1, day13_part1), Part::new(2, day13_part2)],
+        ),
+        Day::new(
+            14,
+            vec![Part::new(1, day14_part1), Part::new(2, day14_part2)],
+        ),
+        Day::new(15, vec![Part::new(2, day15_part2)]),
+        Day::new(
+            16,
+            vec![Part::new(1, day16_part1), Part::new(2, day16_part2)],
+        ),
+        Day::new(
+            17,
+            vec![Part::new(1, day17_part1), Part::new(2, day17_part2)],
+        ),
+        Day::new(18, vec![Part::new(2, day18_part2)]),
+        Day::new(
+            19,
+            vec![Part::new(1, day19_part1), Part::new(2, day19_part2)],
+        ),
+        Day::new(20, vec![Part::new(1, day20_part1)]),
+        Day::new(
+            21,
+            vec![Part::new(1, day21_part1), Part::new(2, day21_part2)],
+        ),
+        Day::new(
+            22,
+            vec![Part::new(1, day22_part1), Part::new(2, day22_part2)],
+        ),
+        Day::new(
+            23,
+            vec![Part::new(1, day23_part1), Part::new(2, day23_part2)],
+        ),
+        Day::new(
+            24,
+            vec![Part::new(1, day24_part1), Part::new(2, day24_part2)],
+        ),
+        Day::new(25, vec![Part::new(1, day25_part1)]),
+    ]
+}
+
+fn day01_solve(
+    input: &Input,
+    solve: fn(&str) -> Result<u64, ParseError>,
+) -> Result<String, Box<dyn Error>> {
+    let (number, captcha) = input
+        .lines()
+        .find(|&(_, l)| !l.trim().is_empty())
+        .unwrap_or((1, ""));
+
+    Ok(solve(captcha.trim())
+        .map_err(|e| input.syntax(number, e))?
+        .to_string())
+}
+
+fn day01_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    day01_solve(input, day01::solve_captcha)
+}
+
+fn day01_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    day01_solve(input, day01::solve_second_captcha)
+}
+
+fn day02_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(Spreadsheet::parse(input)?
+        .checksum(&day02::Spread)?
+        .to_string())
+}
+
+fn day02_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(Spreadsheet::parse(input)?
+        .checksum(&day02::EvenDivision)?
+        .to_string())
+}
+
+fn day03_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let square = input.value()?;
+
+    day03::distance(square)
+        .map(|distance| distance.to_string())
+        .ok_or_else(|| format!("square {} is not on the spiral", square).into())
+}
+
+fn day03_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day03::value_greater_than(input.value()?)?.to_string())
+}
+
+fn day04_passwords(input: &Input) -> Vec<String> {
+    input.lines().map(|(_, l)| l.to_owned()).collect()
+}
+
+fn day04_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day04::count_valid_passwords(&day04_passwords(input)).to_string())
+}
+
+fn day04_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day04::count_passwords_without_anagrams(&day04_passwords(input)).to_string())
+}
+
+fn day05_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day05::number_of_steps(&mut input.values()?).to_string())
+}
+
+fn day05_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day05::number_of_steps_with_decrease(&mut input.values()?).to_string())
+}
+
+fn day06_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let (steps, _) = day06::max_steps_and_cycle_length(&mut input.tokens()?);
+
+    Ok(steps.to_string())
+}
+
+fn day06_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    let (_, cycle_length) = day06::max_steps_and_cycle_length(&mut input.tokens()?);
+
+    Ok(cycle_length.to_string())
+}
+
+fn day07_tower(input: &Input) -> Result<Tower, Box<dyn Error>> {
+    let mut tower = Tower::new();
+
+    for program in input.records()? {
+        tower.add(program);
+    }
+
+    Ok(tower)
+}
+
+fn day07_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    day07_tower(input)?
+        .head()
+        .map(|p| p.to_string())
+        .ok_or_else(|| "no head found".into())
+}
+
+fn day07_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day07_tower(input)?.balanced_weight().to_string())
+}
+
+fn day08_processor(input: &Input) -> Result<Processor, Box<dyn Error>> {
+    let mut processor = Processor::new();
+
+    for instruction in input.records()? {
+        processor.execute(&instruction);
+    }
+
+    Ok(processor)
+}
+
+fn day08_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day08_processor(input)?.largest_value().to_string())
+}
+
+fn day08_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day08_processor(input)?.largest_value_overall().to_string())
+}
+
+fn day09_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day09::parse_stream(input.trimmed()).score.to_string())
+}
+
+fn day09_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day09::parse_stream(input.trimmed()).garbage.to_string())
+}
+
+fn day10_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let (number, lengths) = input
+        .lines()
+        .find(|&(_, l)| !l.trim().is_empty())
+        .unwrap_or((1, ""));
+
+    Ok(day10::check_product(lengths)
+        .map_err(|e| input.syntax(number, e))?
+        .to_string())
+}
+
+fn day10_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day10::hash(input.trimmed().as_bytes()))
+}
+
+fn day11_coords(input: &Input) -> Result<HexCoords, Box<dyn Error>> {
+    let mut coords = HexCoords::new();
+
+    for direction in input.fields(',')? {
+        coords.take_step(direction);
+    }
+
+    Ok(coords)
+}
+
+fn day11_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day11_coords(input)?.distance_from_origin().to_string())
+}
+
+fn day11_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day11_coords(input)?.max_distance_from_origin().to_string())
+}
+
+fn day12_graph(input: &Input) -> Graph {
+    let mut graph = Graph::new();
+
+    for (_, l) in input.lines().filter(|&(_, l)| !l.trim().is_empty()) {
+        graph.parse_node(l);
+    }
+
+    graph
+}
+
+fn day12_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day12_graph(input).nodes_in_group(0).len().to_string())
+}
+
+fn day12_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day12_graph(input).groups().len().to_string())
+}
+
+fn day13_firewall(input: &Input) -> Result<Firewall, Box<dyn Error>> {
+    Ok(Firewall::parse(input)?)
+}
+
+fn day13_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day13_firewall(input)?.trip_severity().to_string())
+}
+
+fn day13_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day13_firewall(input)?.safe_trip_delay().to_string())
+}
+
+fn day14_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(Grid::new(input.trimmed()).used_squares().to_string())
+}
+
+fn day14_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(Grid::new(input.trimmed()).regions().to_string())
+}
+
+fn day15_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    let starting_values = input
+        .lines()
+        .filter_map(|(number, l)| l.split_whitespace().last().map(|v| (number, v)))
+        .map(|(number, v)| input.parse(number, v))
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    if starting_values.len() != 2 {
+        return Err("expected starting values for two generators".into());
+    }
+
+    let judge = Judge::new(starting_values[0], starting_values[1]);
+
+    Ok(judge.count_matches(5_000_000).to_string())
+}
+
+fn day16_order(input: &Input, rounds: usize) -> Result<String, Box<dyn Error>> {
+    let dance_moves = input.fields(',')?;
+
+    Ok(Dance::new(16).order_after(&dance_moves, rounds))
+}
+
+fn day16_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    day16_order(input, 1)
+}
+
+fn day16_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    day16_order(input, 1_000_000_000)
+}
+
+fn day17_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let mut spinlock = Spinlock::new(input.value()?);
+
+    Ok(spinlock.value_after_latest(2017).to_string())
+}
+
+fn day17_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    let spinlock = Spinlock::new(input.value()?);
+
+    Ok(spinlock.value_after_zero(50_000_000).to_string())
+}
+
+fn day18_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    let instructions = input.records()?;
+
+    let mut vm = Vm::new();
+
+    vm.init_program(&instructions);
+    vm.init_program(&instructions);
+
+    Ok(vm.execute().to_string())
+}
+
+fn day19_path(input: &Input) -> (String, u32) {
+    let mut map = Map::new();
+
+    for (_, l) in input.lines() {
+        map.add_row(&l.chars().map(|c| c.into()).collect::<Vec<_>>());
+    }
+
+    map.find_path()
+}
+
+fn day19_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day19_path(input).0)
+}
+
+fn day19_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day19_path(input).1.to_string())
+}
+
+fn day20_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let mut particle_system = ParticleSystem::new();
+
+    for particle in input.records()? {
+        particle_system.add_particle(particle);
+    }
+
+    Ok(particle_system.closest_to_origin().to_string())
+}
+
+fn day21_pixels(input: &Input, iterations: usize) -> Result<String, Box<dyn Error>> {
+    let mut rulebook = Rulebook::new();
+
+    for rule in input.records()? {
+        rulebook.add(rule);
+    }
+
+    rulebook
+        .pixels_on_after(iterations)
+        .map(|pixels| pixels.to_string())
+        .map_err(|pattern| format!("no enhancement rule for {}", pattern).into())
+}
+
+fn day21_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    day21_pixels(input, 5)
+}
+
+fn day21_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    day21_pixels(input, 18)
+}
+
+fn day22_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let mut cluster = Cluster::new(&input.records()?);
+
+    Ok(cluster.infections_after(10_000, Rules::Simple).to_string())
+}
+
+fn day22_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    let mut cluster = Cluster::new(&input.records()?);
+
+    Ok(cluster
+        .infections_after(10_000_000, Rules::Evolved)
+        .to_string())
+}
+
+fn day23_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day23::count_mul(&input.records()?).to_string())
+}
+
+fn day23_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day23::register_h(&input.records()?, true).to_string())
+}
+
+fn day24_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day24::build_bridges(&input.records()?)
+        .strongest
+        .strength
+        .to_string())
+}
+
+fn day24_part2(input: &Input) -> Result<String, Box<dyn Error>> {
+    Ok(day24::build_bridges(&input.records()?)
+        .longest
+        .strength
+        .to_string())
+}
+
+fn day25_part1(input: &Input) -> Result<String, Box<dyn Error>> {
+    let blueprint = day25::Blueprint::parse(input)?;
+
+    Ok(day25::diagnostic_checksum(&blueprint).to_string())
+}
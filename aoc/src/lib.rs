@@ -0,0 +1,394 @@
+extern crate day01;
+extern crate day02;
+extern crate day03;
+extern crate day04;
+extern crate day05;
+extern crate day06;
+extern crate day07;
+extern crate day08;
+extern crate day09;
+extern crate day10;
+extern crate day11;
+extern crate day12;
+extern crate day13;
+extern crate day14;
+extern crate day15;
+extern crate day16;
+extern crate day17;
+extern crate day18;
+extern crate day19;
+extern crate day20;
+extern crate day21;
+extern crate day22;
+extern crate day23;
+extern crate day24;
+extern crate day25;
+extern crate input;
+
+mod answers;
+mod days;
+mod report;
+
+use std::any::Any;
+use std::error;
+use std::fmt;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+
+use input::Input;
+
+pub use answers::{input_hash, Answer, Answers};
+pub use report::Report;
+
+pub type Solver = fn(&Input) -> Result<String, Box<dyn error::Error>>;
+
+pub struct Part {
+    pub number: u32,
+    solve: Solver,
+}
+
+impl Part {
+    pub fn new(number: u32, solve: Solver) -> Self {
+        Part { number, solve }
+    }
+
+    pub fn solve(&self, input: &Input) -> Result<String, Error> {
+        let solve = self.solve;
+
+        match panic::catch_unwind(|| solve(input)) {
+            Ok(result) => result.map_err(Error::Solver),
+            Err(payload) => Err(Error::Panic(panic_message(payload))),
+        }
+    }
+}
+
+pub struct Day {
+    pub number: u32,
+    pub parts: Vec<Part>,
+    default_input: Option<&'static str>,
+}
+
+impl Day {
+    pub fn new(number: u32, parts: Vec<Part>) -> Self {
+        Day {
+            number,
+            parts,
+            default_input: None,
+        }
+    }
+
+    pub fn with_default_input(mut self, input: &'static str) -> Self {
+        self.default_input = Some(input);
+        self
+    }
+
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.number)
+    }
+
+    pub fn part(&self, number: u32) -> Result<&Part, Error> {
+        self.parts
+            .iter()
+            .find(|p| p.number == number)
+            .ok_or(Error::UnknownPart(self.number, number))
+    }
+
+    pub fn input_path(&self, dir: &Path) -> PathBuf {
+        dir.join(self.name()).join("input.txt")
+    }
+
+    pub fn read_input(&self, path: &Path) -> Result<Input, Error> {
+        if path == Path::new("-") {
+            return Input::from_stdin().map_err(Error::Input);
+        }
+
+        match (Input::from_path(path), self.default_input) {
+            (Err(input::Error::Io { ref error, .. }), Some(default))
+                if error.kind() == io::ErrorKind::NotFound =>
+            {
+                Ok(Input::embedded(&self.name(), default))
+            }
+            (result, _) => result.map_err(Error::Input),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownDay(u32),
+    UnknownPart(u32, u32),
+    Input(input::Error),
+    Solver(Box<dyn error::Error>),
+    Panic(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::UnknownDay(day) => write!(f, "no solver for day {}", day),
+            Error::UnknownPart(day, part) => write!(f, "no solver for day {} part {}", day, part),
+            Error::Input(ref e) => write!(f, "{}", e),
+            Error::Solver(ref e) => write!(f, "{}", e),
+            Error::Panic(ref message) => write!(f, "solver panicked: {}", message),
+        }
+    }
+}
+
+impl Error {
+    pub fn is_missing_input(&self) -> bool {
+        match *self {
+            Error::Input(input::Error::Io { ref error, .. }) => {
+                error.kind() == io::ErrorKind::NotFound
+            }
+            _ => false,
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct(String),
+    Incorrect { expected: String, actual: String },
+    Unrecorded(String),
+    // Answers are recorded for this part, but none of them for this input.
+    Changed(String),
+    // Answers are recorded for this part, but there is no input to check them against.
+    MissingInput,
+}
+
+pub fn verify(day: &Day, part: &Part, input: &Input, answers: &Answers) -> Result<Verdict, Error> {
+    let actual = part.solve(input)?;
+
+    Ok(
+        match answers.get(day.number, part.number, &input_hash(input.contents())) {
+            Some(expected) if expected == actual => Verdict::Correct(actual),
+            Some(expected) => Verdict::Incorrect {
+                expected: expected.to_owned(),
+                actual,
+            },
+            None if answers.is_recorded(day.number, part.number) => Verdict::Changed(actual),
+            None => Verdict::Unrecorded(actual),
+        },
+    )
+}
+
+pub fn verify_missing(day: &Day, part: &Part, answers: &Answers) -> Option<Verdict> {
+    if answers.is_recorded(day.number, part.number) {
+        Some(Verdict::MissingInput)
+    } else {
+        None
+    }
+}
+
+pub fn days() -> Vec<Day> {
+    days::all()
+}
+
+pub fn day(number: u32) -> Result<Day, Error> {
+    days()
+        .into_iter()
+        .find(|d| d.number == number)
+        .ok_or(Error::UnknownDay(number))
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_day_is_registered() {
+        let numbers = days().iter().map(|d| d.number).collect::<Vec<_>>();
+
+        assert_eq!((1..26).collect::<Vec<_>>(), numbers);
+    }
+
+    #[test]
+    fn find_day_and_part() {
+        let day = day(7).unwrap();
+
+        assert_eq!(2, day.part(2).unwrap().number);
+    }
+
+    #[test]
+    fn unknown_day() {
+        match day(42) {
+            Err(Error::UnknownDay(42)) => (),
+            _ => panic!("expected unknown day"),
+        }
+    }
+
+    #[test]
+    fn unknown_part() {
+        match day(1).unwrap().part(3) {
+            Err(Error::UnknownPart(1, 3)) => (),
+            _ => panic!("expected unknown part"),
+        }
+    }
+
+    #[test]
+    fn solve_first_day() {
+        let day = day(1).unwrap();
+
+        let first = Input::embedded("test", "91212129\n");
+        let second = Input::embedded("test", "12131415\n");
+
+        assert_eq!("9", day.part(1).unwrap().solve(&first).unwrap());
+        assert_eq!("4", day.part(2).unwrap().solve(&second).unwrap());
+    }
+
+    #[test]
+    fn solve_tower() {
+        let input = Input::embedded(
+            "test",
+            "pbga (66)\nxhth (57)\nebii (61)\nhavc (66)\nktlj (57)\n\
+                     fwft (72) -> ktlj, cntj, xhth\nqoyq (66)\npadx (45) -> pbga, havc, qoyq\n\
+                     tknk (41) -> ugml, padx, fwft\njptl (61)\nugml (68) -> gyxo, ebii, jptl\n\
+                     gyxo (61)\ncntj (57)\n",
+        );
+
+        let day = day(7).unwrap();
+
+        assert_eq!("tknk", day.part(1).unwrap().solve(&input).unwrap());
+        assert_eq!("60", day.part(2).unwrap().solve(&input).unwrap());
+    }
+
+    #[test]
+    fn report_parse_error() {
+        let input = Input::embedded("input.txt", "abcd\n");
+
+        match day(7).unwrap().part(1).unwrap().solve(&input) {
+            Err(Error::Solver(e)) => {
+                assert_eq!(
+                    [
+                        "error: expected a weight, found end of input",
+                        " --> input.txt:1:5",
+                        "  |",
+                        "1 | abcd",
+                        "  |     ^ expected a weight",
+                    ]
+                    .join("\n"),
+                    e.to_string()
+                )
+            }
+            _ => panic!("expected solver error"),
+        }
+    }
+
+    #[test]
+    fn catch_panicking_solver() {
+        let input = Input::embedded("test", "x <-> y\n");
+
+        match day(12).unwrap().part(1).unwrap().solve(&input) {
+            Err(Error::Panic(message)) => assert!(message.starts_with("invalid node index")),
+            _ => panic!("expected panic"),
+        }
+    }
+
+    #[test]
+    fn verify_against_known_answers() {
+        let day = day(1).unwrap();
+        let input = Input::embedded("test", "1122\n");
+
+        let mut answers = Answers::new();
+
+        answers.insert(Answer {
+            day: 1,
+            part: 1,
+            input: input_hash(input.contents()),
+            answer: "3".to_owned(),
+        });
+        answers.insert(Answer {
+            day: 1,
+            part: 2,
+            input: input_hash(input.contents()),
+            answer: "4".to_owned(),
+        });
+
+        assert_eq!(
+            Verdict::Correct("3".to_owned()),
+            verify(&day, day.part(1).unwrap(), &input, &answers).unwrap()
+        );
+        assert_eq!(
+            Verdict::Incorrect {
+                expected: "4".to_owned(),
+                actual: "0".to_owned(),
+            },
+            verify(&day, day.part(2).unwrap(), &input, &answers).unwrap()
+        );
+    }
+
+    #[test]
+    fn verify_changed_input() {
+        let day = day(1).unwrap();
+        let input = Input::embedded("test", "1111\n");
+
+        let mut answers = Answers::new();
+
+        answers.insert(Answer {
+            day: 1,
+            part: 1,
+            input: input_hash("1122\n"),
+            answer: "3".to_owned(),
+        });
+
+        assert_eq!(
+            Verdict::Changed("4".to_owned()),
+            verify(&day, day.part(1).unwrap(), &input, &answers).unwrap()
+        );
+        assert_eq!(
+            Verdict::Unrecorded("4".to_owned()),
+            verify(&day, day.part(2).unwrap(), &input, &answers).unwrap()
+        );
+    }
+
+    #[test]
+    fn verify_missing_input() {
+        let day = day(1).unwrap();
+
+        let mut answers = Answers::new();
+
+        answers.insert(Answer {
+            day: 1,
+            part: 1,
+            input: input_hash("1122\n"),
+            answer: "3".to_owned(),
+        });
+
+        assert_eq!(
+            Some(Verdict::MissingInput),
+            verify_missing(&day, day.part(1).unwrap(), &answers)
+        );
+        assert_eq!(None, verify_missing(&day, day.part(2).unwrap(), &answers));
+    }
+
+    #[test]
+    fn missing_input_falls_back_to_default() {
+        let day = day(3).unwrap();
+
+        let input = day.read_input(Path::new("does/not/exist.txt")).unwrap();
+
+        assert_eq!("day03", input.name());
+        assert_eq!("361527", input.contents());
+    }
+
+    #[test]
+    fn report_missing_input() {
+        match day(7).unwrap().read_input(Path::new("does/not/exist.txt")) {
+            Err(ref e) if e.is_missing_input() => (),
+            _ => panic!("expected missing input"),
+        }
+    }
+}
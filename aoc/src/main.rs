@@ -0,0 +1,359 @@
+extern crate aoc;
+extern crate input;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc::*;
+use input::Input;
+
+const USAGE: &str = "Usage:
+    aoc list
+    aoc run <day> [--part <part>] [--input <path>|-] [--json]
+    aoc all [--dir <path>] [--json]
+    aoc verify [--dir <path>] [--answers <path>]
+    aoc record [--dir <path>] [--answers <path>]";
+
+const ANSWERS: &str = "answers.toml";
+
+enum Command {
+    List,
+    Run {
+        day: u32,
+        part: Option<u32>,
+        input: Option<PathBuf>,
+        json: bool,
+    },
+    All {
+        dir: PathBuf,
+        json: bool,
+    },
+    Verify {
+        dir: PathBuf,
+        answers: PathBuf,
+    },
+    Record {
+        dir: PathBuf,
+        answers: PathBuf,
+    },
+}
+
+fn parse_number(name: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value.ok_or_else(|| format!("missing {}", name))?;
+
+    value
+        .parse()
+        .map_err(|_| format!("invalid {}: {}", name, value))
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("list") => Ok(Command::List),
+        Some("run") => {
+            let day = parse_number("day", args.next())?;
+            let mut part = None;
+            let mut input = None;
+            let mut json = false;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--part" => part = Some(parse_number("part", args.next())?),
+                    "--input" => {
+                        input = Some(PathBuf::from(args.next().ok_or("missing input path")?))
+                    }
+                    "--json" => json = true,
+                    _ => return Err(format!("unknown option: {}", arg)),
+                }
+            }
+
+            Ok(Command::Run {
+                day,
+                part,
+                input,
+                json,
+            })
+        }
+        Some("all") => {
+            let mut dir = PathBuf::from(".");
+            let mut json = false;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--dir" => dir = PathBuf::from(args.next().ok_or("missing directory")?),
+                    "--json" => json = true,
+                    _ => return Err(format!("unknown option: {}", arg)),
+                }
+            }
+
+            Ok(Command::All { dir, json })
+        }
+        Some(command @ "verify") | Some(command @ "record") => {
+            let mut dir = PathBuf::from(".");
+            let mut answers = PathBuf::from(ANSWERS);
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--dir" => dir = PathBuf::from(args.next().ok_or("missing directory")?),
+                    "--answers" => {
+                        answers = PathBuf::from(args.next().ok_or("missing answers path")?)
+                    }
+                    _ => return Err(format!("unknown option: {}", arg)),
+                }
+            }
+
+            if command == "verify" {
+                Ok(Command::Verify { dir, answers })
+            } else {
+                Ok(Command::Record { dir, answers })
+            }
+        }
+        Some(command) => Err(format!("unknown command: {}", command)),
+        None => Err("missing command".to_owned()),
+    }
+}
+
+fn list() {
+    for day in days() {
+        let parts = day
+            .parts
+            .iter()
+            .map(|p| p.number.to_string())
+            .collect::<Vec<_>>();
+
+        println!("Day {:2}: parts {}", day.number, parts.join(", "));
+    }
+}
+
+fn run_parts(day: &Day, parts: &[&Part], input_path: &Path, json: bool) -> bool {
+    let input = match day.read_input(input_path) {
+        Ok(input) => input,
+        Err(ref e) if json => {
+            for part in parts {
+                println!("{}", Report::unread_json(day, part, input_path, e));
+            }
+
+            return false;
+        }
+        Err(e) => {
+            eprintln!("Day {}: {}", day.number, e);
+            return false;
+        }
+    };
+
+    let mut success = true;
+
+    for part in parts {
+        let report = Report::run(day, part, &input);
+
+        if report.answer.is_err() {
+            success = false;
+        }
+
+        match report.answer {
+            _ if json => println!("{}", report.to_json()),
+            Ok(ref answer) => println!("Day {}, part {}: {}", day.number, part.number, answer),
+            Err(ref e) => eprintln!("Day {}, part {} failed: {}", day.number, part.number, e),
+        }
+    }
+
+    success
+}
+
+fn run(day: u32, part: Option<u32>, input: Option<PathBuf>, json: bool) -> Result<bool, Error> {
+    let day = aoc::day(day)?;
+
+    let parts = match part {
+        Some(p) => vec![day.part(p)?],
+        None => day.parts.iter().collect(),
+    };
+
+    let input = input.unwrap_or_else(|| day.input_path(Path::new(".")));
+
+    Ok(run_parts(&day, &parts, &input, json))
+}
+
+fn all(dir: &Path, json: bool) -> bool {
+    let mut success = true;
+
+    for day in days() {
+        let parts = day.parts.iter().collect::<Vec<_>>();
+
+        if !run_parts(&day, &parts, &day.input_path(dir), json) {
+            success = false;
+        }
+    }
+
+    success
+}
+
+fn stored_input(day: &Day, dir: &Path) -> Result<Option<Input>, Error> {
+    match day.read_input(&day.input_path(dir)) {
+        Ok(input) => Ok(Some(input)),
+        Err(ref e) if e.is_missing_input() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Recorded answers that cannot be checked, because the input is gone or has changed, fail
+// the run just like incorrect ones, so that a checkout without inputs does not pass quietly.
+fn verify(dir: &Path, answers: &Answers) -> bool {
+    let (mut correct, mut incorrect, mut unrecorded, mut failed) = (0, 0, 0, 0);
+    let (mut changed, mut missing) = (0, 0);
+
+    for day in days() {
+        let input = match stored_input(&day, dir) {
+            Ok(Some(input)) => input,
+            Ok(None) => {
+                for part in &day.parts {
+                    if let Some(Verdict::MissingInput) = aoc::verify_missing(&day, part, answers) {
+                        missing += 1;
+                        println!(
+                            "Day {}, part {}: answer recorded but {} is missing",
+                            day.number,
+                            part.number,
+                            day.input_path(dir).display()
+                        );
+                    }
+                }
+
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Day {}: {}", day.number, e);
+                failed += day.parts.len();
+                continue;
+            }
+        };
+
+        for part in &day.parts {
+            match aoc::verify(&day, part, &input, answers) {
+                Ok(Verdict::Correct(answer)) => {
+                    correct += 1;
+                    println!("Day {}, part {}: ok ({})", day.number, part.number, answer);
+                }
+                Ok(Verdict::Incorrect { expected, actual }) => {
+                    incorrect += 1;
+                    println!(
+                        "Day {}, part {}: expected {}, got {}",
+                        day.number, part.number, expected, actual
+                    );
+                }
+                Ok(Verdict::Unrecorded(answer)) => {
+                    unrecorded += 1;
+                    println!(
+                        "Day {}, part {}: no recorded answer ({})",
+                        day.number, part.number, answer
+                    );
+                }
+                Ok(Verdict::Changed(answer)) => {
+                    changed += 1;
+                    println!(
+                        "Day {}, part {}: input matches no recorded answer ({})",
+                        day.number, part.number, answer
+                    );
+                }
+                Ok(Verdict::MissingInput) => missing += 1,
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("Day {}, part {} failed: {}", day.number, part.number, e);
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} correct, {} incorrect, {} changed, {} missing, {} unrecorded, {} failed",
+        correct, incorrect, changed, missing, unrecorded, failed
+    );
+
+    incorrect == 0 && changed == 0 && missing == 0 && failed == 0
+}
+
+fn record(dir: &Path, answers: &mut Answers) -> bool {
+    let mut success = true;
+
+    for day in days() {
+        let input = match stored_input(&day, dir) {
+            Ok(Some(input)) => input,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("Day {}: {}", day.number, e);
+                success = false;
+                continue;
+            }
+        };
+
+        for part in &day.parts {
+            match part.solve(&input) {
+                Ok(answer) => {
+                    println!("Day {}, part {}: {}", day.number, part.number, answer);
+                    answers.insert(Answer {
+                        day: day.number,
+                        part: part.number,
+                        input: input_hash(input.contents()),
+                        answer,
+                    });
+                }
+                Err(e) => {
+                    eprintln!("Day {}, part {} failed: {}", day.number, part.number, e);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    success
+}
+
+fn main() {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let success = match command {
+        Command::List => {
+            list();
+            true
+        }
+        Command::Run {
+            day,
+            part,
+            input,
+            json,
+        } => match run(day, part, input, json) {
+            Ok(success) => success,
+            Err(ref e) if json => {
+                println!("{}", Report::unknown_json(day, part, e));
+                false
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                false
+            }
+        },
+        Command::All { dir, json } => all(&dir, json),
+        Command::Verify { dir, answers } => {
+            verify(&dir, &Answers::load(&answers).unwrap_or_else(|e| e.exit()))
+        }
+        Command::Record { dir, answers: path } => {
+            let mut answers = Answers::load(&path).unwrap_or_else(|e| e.exit());
+            let success = record(&dir, &mut answers);
+
+            if let Err(e) = answers.save(&path) {
+                eprintln!("{}: {}", path.display(), e);
+                process::exit(1);
+            }
+
+            success
+        }
+    };
+
+    if !success {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use input::Input;
+
+use {Day, Error, Part};
+
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: Result<String, Error>,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn run(day: &Day, part: &Part, input: &Input) -> Self {
+        let start = Instant::now();
+        let answer = part.solve(input);
+
+        Report {
+            day: day.number,
+            part: part.number,
+            input: input.name().to_owned(),
+            answer,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let answer = match self.answer {
+            Ok(ref answer) => format!("\"answer\":{}", quote(answer)),
+            Err(ref e) => format!("\"answer\":null,\"error\":{}", quote(&e.to_string())),
+        };
+
+        json(self.day, self.part, &answer, self.elapsed, &self.input)
+    }
+
+    // A part that never ran because its input could not be read still gets a record, so
+    // that consumers of the JSON output can tell it apart from a part that was not asked for.
+    pub fn unread_json(day: &Day, part: &Part, input: &Path, error: &Error) -> String {
+        let answer = format!("\"answer\":null,\"error\":{}", quote(&error.to_string()));
+
+        json(
+            day.number,
+            part.number,
+            &answer,
+            Duration::default(),
+            &input.display().to_string(),
+        )
+    }
+
+    // A day or part without a solver has no input or timing to report, only the error.
+    pub fn unknown_json(day: u32, part: Option<u32>, error: &Error) -> String {
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":null,\"error\":{}}}",
+            day,
+            part.map_or_else(|| "null".to_owned(), |p| p.to_string()),
+            quote(&error.to_string())
+        )
+    }
+}
+
+fn json(day: u32, part: u32, answer: &str, elapsed: Duration, input: &str) -> String {
+    format!(
+        "{{\"day\":{},\"part\":{},{},\"elapsed_ms\":{:.3},\"input\":{}}}",
+        day,
+        part,
+        answer,
+        elapsed.as_secs_f64() * 1000.0,
+        quote(input)
+    )
+}
+
+// The escapes are shared by JSON and the TOML answers file, so every quoted string stays on
+// a single line in both.
+pub fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(answer: Result<String, Error>) -> Report {
+        Report {
+            day: 7,
+            part: 1,
+            input: "day07/input.txt".to_owned(),
+            answer,
+            elapsed: Duration::from_micros(1500),
+        }
+    }
+
+    #[test]
+    fn report_answer_as_json() {
+        assert_eq!(
+            "{\"day\":7,\"part\":1,\"answer\":\"tknk\",\"elapsed_ms\":1.500,\"input\":\"day07/input.txt\"}",
+            report(Ok("tknk".to_owned())).to_json()
+        );
+    }
+
+    #[test]
+    fn report_error_as_json() {
+        assert_eq!(
+            "{\"day\":7,\"part\":1,\"answer\":null,\"error\":\"solver panicked: bad \\\"x\\\"\\n\",\
+             \"elapsed_ms\":1.500,\"input\":\"day07/input.txt\"}",
+            report(Err(Error::Panic("bad \"x\"\n".to_owned()))).to_json()
+        );
+    }
+
+    #[test]
+    fn report_unread_input_as_json() {
+        let day = Day::new(7, vec![Part::new(2, |_| Ok(String::new()))]);
+        let error = Error::UnknownDay(26);
+
+        assert_eq!(
+            "{\"day\":7,\"part\":2,\"answer\":null,\"error\":\"no solver for day 26\",\
+             \"elapsed_ms\":0.000,\"input\":\"day07/input.txt\"}",
+            Report::unread_json(&day, &day.parts[0], Path::new("day07/input.txt"), &error)
+        );
+    }
+
+    #[test]
+    fn report_unknown_day_and_part_as_json() {
+        assert_eq!(
+            "{\"day\":26,\"part\":null,\"answer\":null,\"error\":\"no solver for day 26\"}",
+            Report::unknown_json(26, None, &Error::UnknownDay(26))
+        );
+        assert_eq!(
+            "{\"day\":15,\"part\":1,\"answer\":null,\"error\":\"no solver for day 15 part 1\"}",
+            Report::unknown_json(15, Some(1), &Error::UnknownPart(15, 1))
+        );
+    }
+
+    #[test]
+    fn escape_control_characters() {
+        assert_eq!("\"a\\tb\\u0001\\\\\"", quote("a\tb\u{1}\\"));
+    }
+}
@@ -0,0 +1,566 @@
+use std::collections::{BTreeSet, HashSet};
+
+use Rng;
+
+pub fn generate(day: u32) -> Option<String> {
+    let mut rng = Rng::new(u64::from(day));
+
+    let input = match day {
+        1 => captcha(&mut rng),
+        2 => spreadsheet(&mut rng),
+        3 => rng.range(300_000, 400_000).to_string(),
+        4 => passphrases(&mut rng),
+        5 => jump_offsets(&mut rng),
+        6 => memory_banks(&mut rng),
+        7 => tower(&mut rng),
+        8 => register_instructions(&mut rng),
+        9 => stream(&mut rng),
+        10 => knot_lengths(&mut rng),
+        11 => hex_steps(&mut rng),
+        12 => pipes(&mut rng),
+        13 => firewall(&mut rng),
+        14 => word(&mut rng, 8, 26),
+        15 => format!(
+            "Generator A starts with {}\nGenerator B starts with {}\n",
+            rng.range(1, 1000),
+            rng.range(1, 1000)
+        ),
+        16 => dance(&mut rng),
+        17 => rng.range(300, 400).to_string(),
+        18 => duet(&mut rng),
+        19 => routing_diagram(&mut rng),
+        20 => particles(&mut rng),
+        21 => enhancement_rules(&mut rng),
+        22 => virus_map(&mut rng),
+        23 => coprocessor(&mut rng),
+        24 => components(&mut rng),
+        25 => blueprint(&mut rng),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+fn word(rng: &mut Rng, length: usize, letters: u8) -> String {
+    (0..length)
+        .map(|_| (b'a' + rng.below(u64::from(letters)) as u8) as char)
+        .collect()
+}
+
+fn lines<I: IntoIterator<Item = String>>(lines: I) -> String {
+    lines.into_iter().map(|l| l + "\n").collect()
+}
+
+fn captcha(rng: &mut Rng) -> String {
+    (0..2000)
+        .map(|_| (b'1' + rng.below(9) as u8) as char)
+        .collect()
+}
+
+fn primes(limit: u64) -> Vec<u64> {
+    (2..limit)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect()
+}
+
+// Distinct primes never divide each other, so replacing one of them with a small multiple
+// of another leaves exactly one evenly divisible pair in every row.
+fn spreadsheet(rng: &mut Rng) -> String {
+    let primes = primes(2000)
+        .into_iter()
+        .filter(|&p| p > 10)
+        .collect::<Vec<_>>();
+
+    lines((0..16).map(|_| {
+        let mut row = BTreeSet::new();
+
+        while row.len() < 16 {
+            row.insert(*rng.choose(&primes));
+        }
+
+        let mut row = row.into_iter().collect::<Vec<_>>();
+        let divisor = rng.below(16) as usize;
+        let multiple = (divisor + 1 + rng.below(15) as usize) % 16;
+
+        row[multiple] = row[divisor] * rng.range(2, 9) as u64;
+
+        row.iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join("\t")
+    }))
+}
+
+fn passphrases(rng: &mut Rng) -> String {
+    lines((0..512).map(|_| {
+        let count = rng.range(5, 10);
+
+        (0..count)
+            .map(|_| {
+                let length = rng.range(2, 7) as usize;
+                word(rng, length, 8)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }))
+}
+
+fn jump_offsets(rng: &mut Rng) -> String {
+    lines((0..1000).map(|i| rng.range(-i, 2).to_string()))
+}
+
+fn memory_banks(rng: &mut Rng) -> String {
+    (0..16)
+        .map(|_| rng.below(16).to_string())
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+struct Template {
+    weight: u32,
+    children: usize,
+    child: Option<Box<Template>>,
+}
+
+fn template(rng: &mut Rng, depth: u32) -> Template {
+    let child = if depth > 0 {
+        Some(Box::new(template(rng, depth - 1)))
+    } else {
+        None
+    };
+
+    Template {
+        weight: rng.range(10, 100) as u32,
+        children: if child.is_some() {
+            rng.range(3, 5) as usize
+        } else {
+            0
+        },
+        child,
+    }
+}
+
+fn unique_name(rng: &mut Rng, names: &mut HashSet<String>) -> String {
+    loop {
+        let length = rng.range(4, 7) as usize;
+        let name = word(rng, length, 26);
+
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+fn instantiate(
+    rng: &mut Rng,
+    template: &Template,
+    names: &mut HashSet<String>,
+    programs: &mut Vec<(String, u32, Vec<String>)>,
+) -> String {
+    let name = unique_name(rng, names);
+    let index = programs.len();
+
+    programs.push((name.clone(), template.weight, Vec::new()));
+
+    if let Some(ref child) = template.child {
+        for _ in 0..template.children {
+            let child = instantiate(rng, child, names, programs);
+            programs[index].2.push(child);
+        }
+    }
+
+    name
+}
+
+// Siblings are copies of the same template so the tower is balanced, then a single program
+// other than the root is made heavier.
+fn tower(rng: &mut Rng) -> String {
+    let template = template(rng, 4);
+
+    let mut names = HashSet::new();
+    let mut programs = Vec::new();
+
+    instantiate(rng, &template, &mut names, &mut programs);
+
+    let unbalanced = 1 + rng.below(programs.len() as u64 - 1) as usize;
+    programs[unbalanced].1 += rng.range(1, 9) as u32;
+
+    lines(programs.into_iter().map(|(name, weight, children)| {
+        if children.is_empty() {
+            format!("{} ({})", name, weight)
+        } else {
+            format!("{} ({}) -> {}", name, weight, children.join(", "))
+        }
+    }))
+}
+
+fn register_instructions(rng: &mut Rng) -> String {
+    let registers = (0..26).map(|_| word(rng, 3, 26)).collect::<Vec<_>>();
+    let operations = ["inc", "dec"];
+    let conditions = [">", "<", ">=", "<=", "==", "!="];
+
+    lines((0..1000).map(|_| {
+        format!(
+            "{} {} {} if {} {} {}",
+            rng.choose(&registers),
+            rng.choose(&operations),
+            rng.range(-1000, 1000),
+            rng.choose(&registers),
+            rng.choose(&conditions),
+            rng.range(-10, 10)
+        )
+    }))
+}
+
+fn garbage(rng: &mut Rng, stream: &mut String) {
+    stream.push('<');
+
+    for _ in 0..rng.range(0, 12) {
+        match rng.below(6) {
+            0 => {
+                stream.push('!');
+                stream.push(*rng.choose(&['>', '!', 'a', '<']));
+            }
+            _ => stream.push(*rng.choose(&['a', 'e', 'i', 'o', 'u', '{', '}', '<', ','])),
+        }
+    }
+
+    stream.push('>');
+}
+
+fn group(rng: &mut Rng, depth: u32, stream: &mut String) {
+    stream.push('{');
+
+    let count = if depth < 6 { rng.range(0, 4) } else { 0 };
+
+    for i in 0..count {
+        if i > 0 {
+            stream.push(',');
+        }
+
+        if rng.below(3) == 0 {
+            garbage(rng, stream);
+        } else {
+            group(rng, depth + 1, stream);
+        }
+    }
+
+    stream.push('}');
+}
+
+fn stream(rng: &mut Rng) -> String {
+    let mut stream = String::from("{");
+
+    while stream.len() < 16_000 {
+        if stream.len() > 1 {
+            stream.push(',');
+        }
+
+        group(rng, 1, &mut stream);
+    }
+
+    stream.push('}');
+    stream
+}
+
+fn knot_lengths(rng: &mut Rng) -> String {
+    (0..16)
+        .map(|_| rng.below(256).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn hex_steps(rng: &mut Rng) -> String {
+    let directions = ["n", "ne", "se", "s", "sw", "nw", "n", "ne"];
+
+    (0..8000)
+        .map(|_| *rng.choose(&directions))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Pipes only connect programs within the same block of 16, which keeps the groups small
+// and numerous like in the puzzle input.
+fn pipes(rng: &mut Rng) -> String {
+    let count = 2000;
+    let mut neighbours = vec![BTreeSet::new(); count];
+
+    for node in 0..count {
+        neighbours[node].insert(node);
+
+        for _ in 0..rng.range(0, 1) {
+            let other = (node / 16 * 16 + rng.below(16) as usize).min(count - 1);
+
+            neighbours[node].insert(other);
+            neighbours[other].insert(node);
+        }
+    }
+
+    lines(neighbours.iter().enumerate().map(|(node, n)| {
+        let n = n.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+        format!("{} <-> {}", node, n.join(", "))
+    }))
+}
+
+fn caught(delay: i64, &(depth, range): &(i64, i64)) -> bool {
+    (delay + depth) % (2 * (range - 1)) == 0
+}
+
+// Layers are added one at a time to catch the earliest safe delay so far, but never the
+// target delay, so the brute-force search has real work to do and is guaranteed to finish.
+fn firewall(rng: &mut Rng) -> String {
+    let target = rng.range(100_000, 1_000_000);
+
+    let mut depths = (0..90).collect::<Vec<i64>>();
+    let mut layers = Vec::new();
+    let mut delay = 0;
+
+    while !depths.is_empty() {
+        while layers.iter().any(|layer| caught(delay, layer)) {
+            delay += 1;
+        }
+
+        if delay == target {
+            break;
+        }
+
+        let depth = depths.swap_remove(rng.below(depths.len() as u64) as usize);
+        let ranges = (2..17)
+            .filter(|&range| caught(delay, &(depth, range)) && !caught(target, &(depth, range)))
+            .collect::<Vec<_>>();
+
+        if !ranges.is_empty() {
+            layers.push((depth, *rng.choose(&ranges)));
+        }
+    }
+
+    layers.sort();
+
+    lines(
+        layers
+            .iter()
+            .map(|&(depth, range)| format!("{}: {}", depth, range)),
+    )
+}
+
+fn dance(rng: &mut Rng) -> String {
+    (0..10_000)
+        .map(|_| match rng.below(3) {
+            0 => format!("s{}", rng.range(1, 15)),
+            1 => format!("x{}/{}", rng.below(16), rng.below(16)),
+            _ => {
+                let a = (b'a' + rng.below(16) as u8) as char;
+                let b = (b'a' + rng.below(16) as u8) as char;
+                format!("p{}/{}", a, b)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Both programs send a stream of pseudo-random values, then receive exactly as many from
+// each other and halt. A deadlock would only end at the receive timeout, which would make
+// the timing meaningless.
+fn duet(rng: &mut Rng) -> String {
+    let program = [
+        "set i 127".to_owned(),
+        format!("set a {}", rng.range(1, 1_000_000)),
+        "mul a 8505".to_owned(),
+        "mod a 2147483647".to_owned(),
+        "mul a 129749".to_owned(),
+        "add a 12345".to_owned(),
+        "mod a 2147483647".to_owned(),
+        "set b a".to_owned(),
+        "mod b 10000".to_owned(),
+        "snd b".to_owned(),
+        "add i -1".to_owned(),
+        "jgz i -9".to_owned(),
+        "set i 127".to_owned(),
+        "rcv b".to_owned(),
+        "add i -1".to_owned(),
+        "jgz i -2".to_owned(),
+    ];
+
+    lines(program.iter().cloned())
+}
+
+// A serpentine path: vertical runs three columns apart, joined alternately at the bottom
+// row and just below the top row, with letters scattered along the verticals.
+fn routing_diagram(rng: &mut Rng) -> String {
+    let (columns, height) = (20, 100);
+    let mut grid = vec![vec![' '; 3 * columns + 2]; height + 1];
+
+    for column in 0..columns {
+        let x = 1 + 3 * column;
+        let top = if column == 0 { 0 } else { 2 };
+
+        for (row, line) in grid.iter_mut().enumerate().take(height).skip(top) {
+            line[x] = if row > 0 && rng.below(10) == 0 {
+                (b'A' + rng.below(26) as u8) as char
+            } else {
+                '|'
+            };
+        }
+
+        if column > 0 {
+            let row = if column % 2 == 1 { height } else { 1 };
+
+            grid[row][(x - 3)..(x + 1)].copy_from_slice(&['+', '-', '-', '+']);
+        }
+    }
+
+    let x = 1 + 3 * (columns - 1);
+    let end = height / 2;
+    let cleared = if columns % 2 == 0 {
+        2..end
+    } else {
+        (end + 1)..height
+    };
+
+    for line in &mut grid[cleared] {
+        line[x] = ' ';
+    }
+
+    grid[end][x] = 'Z';
+
+    lines(grid.into_iter().map(|line| line.into_iter().collect()))
+}
+
+fn particles(rng: &mut Rng) -> String {
+    let mut vector = |limit: i64| {
+        format!(
+            "<{},{},{}>",
+            rng.range(-limit, limit),
+            rng.range(-limit, limit),
+            rng.range(-limit, limit)
+        )
+    };
+
+    lines((0..1000).map(|_| {
+        let p = vector(1500);
+        let v = vector(100);
+        let a = vector(10);
+
+        format!("p={}, v={}, a={}", p, v, a)
+    }))
+}
+
+fn pattern(size: usize, bits: u32) -> String {
+    (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| {
+                    if bits & (1 << (row * size + col)) != 0 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// One rule for every possible 2x2 and 3x3 pattern, so no enhancement can get stuck.
+fn enhancement_rules(rng: &mut Rng) -> String {
+    lines([2, 3].iter().flat_map(|&size| {
+        (0..(1 << (size * size)))
+            .map(|bits| {
+                let output = rng.below(1 << ((size + 1) * (size + 1))) as u32;
+
+                format!("{} => {}", pattern(size, bits), pattern(size + 1, output))
+            })
+            .collect::<Vec<_>>()
+    }))
+}
+
+fn virus_map(rng: &mut Rng) -> String {
+    lines((0..25).map(|_| {
+        (0..25)
+            .map(|_| if rng.below(2) == 0 { '#' } else { '.' })
+            .collect()
+    }))
+}
+
+fn coprocessor(rng: &mut Rng) -> String {
+    let program = [
+        "set c b",
+        "jnz a 2",
+        "jnz 1 5",
+        "mul b 100",
+        "sub b -100000",
+        "set c b",
+        "sub c -17000",
+        "set f 1",
+        "set d 2",
+        "set e 2",
+        "set g d",
+        "mul g e",
+        "sub g b",
+        "jnz g 2",
+        "set f 0",
+        "sub e -1",
+        "set g e",
+        "sub g b",
+        "jnz g -8",
+        "sub d -1",
+        "set g d",
+        "sub g b",
+        "jnz g -13",
+        "jnz f 2",
+        "sub h -1",
+        "set g b",
+        "sub g c",
+        "jnz g 2",
+        "jnz 1 3",
+        "sub b -17",
+        "jnz 1 -23",
+    ];
+
+    format!("set b {}\n", rng.range(57, 99)) + &lines(program.iter().map(|l| l.to_string()))
+}
+
+fn components(rng: &mut Rng) -> String {
+    let mut pairs = vec![(0, rng.range(1, 50))];
+
+    while pairs.len() < 56 {
+        let a = rng.range(0, 50);
+        let b = if rng.below(10) == 0 {
+            a
+        } else {
+            rng.range(1, 50)
+        };
+
+        if !pairs.contains(&(a, b)) && !pairs.contains(&(b, a)) {
+            pairs.push((a, b));
+        }
+    }
+
+    lines(pairs.into_iter().map(|(a, b)| format!("{}/{}", a, b)))
+}
+
+fn blueprint(rng: &mut Rng) -> String {
+    let states = ['A', 'B', 'C', 'D', 'E', 'F'];
+    let mut blueprint = format!(
+        "Begin in state A.\nPerform a diagnostic checksum after {} steps.\n",
+        rng.range(12_000_000, 13_000_000)
+    );
+
+    for state in &states {
+        blueprint += &format!("\nIn state {}:\n", state);
+
+        for value in 0..2 {
+            blueprint += &format!("  If the current value is {}:\n", value);
+            blueprint += &format!("    - Write the value {}.\n", rng.below(2));
+            blueprint += &format!(
+                "    - Move one slot to the {}.\n",
+                rng.choose(&["left", "right"])
+            );
+            blueprint += &format!("    - Continue with state {}.\n", rng.choose(&states));
+        }
+    }
+
+    blueprint
+}
@@ -0,0 +1,202 @@
+extern crate aoc;
+extern crate input;
+
+mod inputs;
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use aoc::{Day, Error};
+use input::Input;
+
+pub use inputs::generate;
+
+const MIN_SAMPLES: usize = 3;
+const MAX_SAMPLES: usize = 100;
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng {
+            state: seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let total = samples.iter().sum::<Duration>();
+
+        Stats {
+            iterations: samples.len(),
+            mean: total / samples.len().max(1) as u32,
+            min: samples.iter().min().cloned().unwrap_or_default(),
+            max: samples.iter().max().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+pub fn measure<F: FnMut()>(budget: Duration, mut f: F) -> Stats {
+    f();
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+
+    while samples.len() < MIN_SAMPLES || (samples.len() < MAX_SAMPLES && start.elapsed() < budget) {
+        let sample = Instant::now();
+        f();
+        samples.push(sample.elapsed());
+    }
+
+    Stats::from_samples(&samples)
+}
+
+pub struct Timing(pub Duration);
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nanos = self.0.as_secs_f64() * 1e9;
+
+        let (value, unit) = if nanos < 1e3 {
+            (nanos, "ns")
+        } else if nanos < 1e6 {
+            (nanos / 1e3, "µs")
+        } else if nanos < 1e9 {
+            (nanos / 1e6, "ms")
+        } else {
+            (nanos / 1e9, "s")
+        };
+
+        let formatted = format!("{:.2} {}", value, unit);
+
+        match f.width() {
+            Some(width) => write!(f, "{:>width$}", formatted, width = width),
+            None => write!(f, "{}", formatted),
+        }
+    }
+}
+
+pub struct Benchmark {
+    pub day: u32,
+    pub part: u32,
+    pub stats: Stats,
+}
+
+pub fn bench_day(day: &Day, budget: Duration) -> Result<Vec<Benchmark>, Error> {
+    let contents = generate(day.number).ok_or(Error::UnknownDay(day.number))?;
+    let input = Input::embedded(&day.name(), &contents);
+
+    let mut benchmarks = Vec::new();
+
+    for part in &day.parts {
+        part.solve(&input)?;
+
+        let stats = measure(budget, || {
+            let _ = part.solve(&input);
+        });
+
+        benchmarks.push(Benchmark {
+            day: day.number,
+            part: part.number,
+            stats,
+        });
+    }
+
+    Ok(benchmarks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_rng() {
+        let first = (0..4).map(|_| Rng::new(7).next_u64()).collect::<Vec<_>>();
+
+        assert!(first.iter().all(|&v| v == first[0]));
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn range_is_inclusive() {
+        let mut rng = Rng::new(1);
+
+        let values = (0..1000).map(|_| rng.range(-2, 2)).collect::<Vec<_>>();
+
+        assert_eq!(Some(&-2), values.iter().min());
+        assert_eq!(Some(&2), values.iter().max());
+    }
+
+    #[test]
+    fn summarize_samples() {
+        let samples = [
+            Duration::from_millis(2),
+            Duration::from_millis(4),
+            Duration::from_millis(6),
+        ];
+
+        assert_eq!(
+            Stats {
+                iterations: 3,
+                mean: Duration::from_millis(4),
+                min: Duration::from_millis(2),
+                max: Duration::from_millis(6),
+            },
+            Stats::from_samples(&samples)
+        );
+    }
+
+    #[test]
+    fn format_timings() {
+        assert_eq!("512.00 ns", Timing(Duration::from_nanos(512)).to_string());
+        assert_eq!("1.50 ms", Timing(Duration::from_micros(1500)).to_string());
+        assert_eq!("   2.00 s", format!("{:9}", Timing(Duration::from_secs(2))));
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for number in &[1, 2, 4, 6, 7, 8, 9, 10, 11, 12, 16, 19, 20] {
+            let day = aoc::day(*number).unwrap();
+            let input = Input::embedded(&day.name(), &generate(day.number).unwrap());
+
+            for part in &day.parts {
+                assert!(
+                    part.solve(&input).is_ok(),
+                    "day {} part {}",
+                    day.number,
+                    part.number
+                );
+            }
+        }
+    }
+}
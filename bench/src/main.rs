@@ -0,0 +1,99 @@
+extern crate aoc;
+extern crate bench;
+
+use std::env;
+use std::process;
+use std::time::Duration;
+
+use bench::{Benchmark, Timing};
+
+const USAGE: &str = "Usage:
+    bench [<day>...] [--time <ms>]";
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<(Vec<u32>, Duration), String> {
+    let mut days = Vec::new();
+    let mut budget = Duration::from_secs(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--time" => {
+                let value = args.next().ok_or("missing time")?;
+                let millis = value
+                    .parse()
+                    .map_err(|_| format!("invalid time: {}", value))?;
+
+                budget = Duration::from_millis(millis);
+            }
+            _ => days.push(arg.parse().map_err(|_| format!("invalid day: {}", arg))?),
+        }
+    }
+
+    Ok((days, budget))
+}
+
+fn summary(benchmarks: &[Benchmark]) {
+    println!("\n Day  Part        Mean         Min         Max  Iterations");
+
+    for b in benchmarks {
+        println!(
+            "{:4}  {:4}  {:10}  {:10}  {:10}  {:10}",
+            b.day,
+            b.part,
+            Timing(b.stats.mean),
+            Timing(b.stats.min),
+            Timing(b.stats.max),
+            b.stats.iterations
+        );
+    }
+
+    let total = benchmarks.iter().map(|b| b.stats.mean).sum();
+
+    println!("\nTotal of means: {}", Timing(total));
+}
+
+fn main() {
+    let (numbers, budget) = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let days = aoc::days()
+        .into_iter()
+        .filter(|d| numbers.is_empty() || numbers.contains(&d.number))
+        .collect::<Vec<_>>();
+
+    let mut benchmarks = Vec::new();
+    let mut success = true;
+
+    for day in &days {
+        println!("{}", day.name());
+
+        match bench::bench_day(day, budget) {
+            Ok(results) => {
+                for b in &results {
+                    println!(
+                        "  part {}: {} per iteration ({} iterations)",
+                        b.part,
+                        Timing(b.stats.mean),
+                        b.stats.iterations
+                    );
+                }
+
+                benchmarks.extend(results);
+            }
+            Err(e) => {
+                eprintln!("  failed: {}", e);
+                success = false;
+            }
+        }
+    }
+
+    summary(&benchmarks);
+
+    if !success {
+        process::exit(1);
+    }
+}
@@ -1,34 +1,175 @@
-pub fn solve_captcha(captcha: &str) -> u32 {
-    captcha
-        .chars()
-        .cycle()
-        .take(captcha.len() + 1)
-        .collect::<Vec<_>>()
-        .windows(2)
-        .map(|a| if a.len() == 2 && a[0] == a[1] {
-            a[0].to_digit(10).unwrap_or(0)
-        } else {
-            0
-        })
-        .sum()
+extern crate input;
+
+use std::error;
+use std::fmt;
+use std::io::{self, Read};
+
+use input::ParseError;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+fn digits(captcha: &str) -> Result<&[u8], ParseError> {
+    match captcha.bytes().position(|b| !b.is_ascii_digit()) {
+        Some(i) => {
+            let len = captcha[i..].chars().next().map_or(1, char::len_utf8);
+            Err(ParseError::new(captcha, i..(i + len), "a digit"))
+        }
+        None => Ok(captcha.as_bytes()),
+    }
 }
 
-pub fn solve_second_captcha(captcha: &str) -> u32 {
-    captcha
-        .chars()
+// Comparing against a rotated view of the same slice keeps this a single linear pass,
+// without any index arithmetic per digit.
+fn matches<'a>(digits: &'a [u8], offset: usize) -> impl Iterator<Item = (usize, u8)> + 'a {
+    let offset = if digits.is_empty() {
+        0
+    } else {
+        offset % digits.len()
+    };
+    let rotated = digits[offset..].iter().chain(&digits[..offset]);
+
+    digits
+        .iter()
+        .zip(rotated)
         .enumerate()
-        .map(|(index, c)| if c ==
-            captcha
-                .chars()
-                .cycle()
-                .nth(captcha.len() / 2 + index)
-                .unwrap()
-        {
-            c.to_digit(10).unwrap_or(0)
+        .filter(|&(_, (a, b))| a == b)
+        .map(|(i, (&digit, _))| (i, digit - b'0'))
+}
+
+pub fn matching_positions(captcha: &str, offset: usize) -> Result<Vec<usize>, ParseError> {
+    Ok(matches(digits(captcha)?, offset).map(|(i, _)| i).collect())
+}
+
+pub fn solve_captcha_with_offset(captcha: &str, offset: usize) -> Result<u64, ParseError> {
+    Ok(matches(digits(captcha)?, offset)
+        .map(|(_, d)| u64::from(d))
+        .sum())
+}
+
+pub fn solve_captcha(captcha: &str) -> Result<u64, ParseError> {
+    solve_captcha_with_offset(captcha, 1)
+}
+
+pub fn solve_second_captcha(captcha: &str) -> Result<u64, ParseError> {
+    solve_captcha_with_offset(captcha, captcha.len() / 2)
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    NotADigit { offset: u64, byte: u8 },
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StreamError::Io(ref e) => write!(f, "{}", e),
+            StreamError::NotADigit { offset, byte } => {
+                write!(
+                    f,
+                    "expected a digit at byte {}, found {:?}",
+                    offset, byte as char
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            StreamError::Io(ref e) => Some(e),
+            StreamError::NotADigit { .. } => None,
+        }
+    }
+}
+
+// Trailing whitespace is skipped so that a newline from a pipe or a file is not an error.
+// Anywhere else it is rejected once the next digit shows it was not trailing after all, just
+// as the in-memory solvers reject it.
+fn for_each_digit<R: Read, F: FnMut(u8)>(mut reader: R, mut f: F) -> Result<(), StreamError> {
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut offset = 0;
+    let mut whitespace = None;
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(read) => read,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(StreamError::Io(e)),
+        };
+
+        for (i, &byte) in buffer[..read].iter().enumerate() {
+            match byte {
+                b'0'..=b'9' => {
+                    if let Some((offset, byte)) = whitespace {
+                        return Err(StreamError::NotADigit { offset, byte });
+                    }
+
+                    f(byte - b'0')
+                }
+                _ if byte.is_ascii_whitespace() => {
+                    whitespace = whitespace.or(Some((offset + i as u64, byte)));
+                }
+                _ => {
+                    return Err(StreamError::NotADigit {
+                        offset: offset + i as u64,
+                        byte,
+                    })
+                }
+            }
+        }
+
+        offset += read as u64;
+    }
+}
+
+pub fn stream_captcha<R: Read>(reader: R) -> Result<u64, StreamError> {
+    let mut first = None;
+    let mut previous = None;
+    let mut sum = 0;
+
+    for_each_digit(reader, |digit| {
+        if previous == Some(digit) {
+            sum += u64::from(digit);
+        }
+
+        first = first.or(Some(digit));
+        previous = Some(digit);
+    })?;
+
+    if first.is_some() && first == previous {
+        sum += u64::from(previous.unwrap_or(0));
+    }
+
+    Ok(sum)
+}
+
+// The halfway point is only known once the whole stream has been read, so every digit has
+// to be kept until then, packed two to a byte.
+pub fn stream_second_captcha<R: Read>(reader: R) -> Result<u64, StreamError> {
+    let mut packed = Vec::new();
+    let mut len = 0;
+
+    for_each_digit(reader, |digit| {
+        if len % 2 == 0 {
+            packed.push(digit);
         } else {
-            0
-        })
-        .sum()
+            packed[len / 2] |= digit << 4;
+        }
+
+        len += 1;
+    })?;
+
+    let digit = |i: usize| (packed[i / 2] >> (4 * (i % 2))) & 0xf;
+    let half = len / 2;
+
+    Ok((0..len)
+        .map(|i| (digit(i), digit((i + half) % len)))
+        .filter(|&(a, b)| a == b)
+        .map(|(a, _)| u64::from(a))
+        .sum())
 }
 
 #[cfg(test)]
@@ -37,46 +178,139 @@ mod tests {
 
     #[test]
     fn two_matches() {
-        assert_eq!(3, solve_captcha("1122"));
+        assert_eq!(Ok(3), solve_captcha("1122"));
     }
 
     #[test]
     fn all_digit_matches() {
-        assert_eq!(4, solve_captcha("1111"));
+        assert_eq!(Ok(4), solve_captcha("1111"));
     }
 
     #[test]
     fn no_matches() {
-        assert_eq!(0, solve_captcha("1234"));
+        assert_eq!(Ok(0), solve_captcha("1234"));
     }
 
     #[test]
     fn last_digit_matches() {
-        assert_eq!(9, solve_captcha("91212129"));
+        assert_eq!(Ok(9), solve_captcha("91212129"));
     }
 
     #[test]
     fn second_captcha_all_digit_matches() {
-        assert_eq!(6, solve_second_captcha("1212"));
+        assert_eq!(Ok(6), solve_second_captcha("1212"));
     }
 
     #[test]
     fn second_captcha_no_matches() {
-        assert_eq!(0, solve_second_captcha("1221"));
+        assert_eq!(Ok(0), solve_second_captcha("1221"));
     }
 
     #[test]
     fn second_captcha_two_matches() {
-        assert_eq!(4, solve_second_captcha("123425"));
+        assert_eq!(Ok(4), solve_second_captcha("123425"));
     }
 
     #[test]
     fn second_captcha_three_matches() {
-        assert_eq!(12, solve_second_captcha("123123"));
+        assert_eq!(Ok(12), solve_second_captcha("123123"));
     }
 
     #[test]
     fn second_captcha_ones_match() {
-        assert_eq!(4, solve_second_captcha("12131415"));
+        assert_eq!(Ok(4), solve_second_captcha("12131415"));
+    }
+
+    #[test]
+    fn custom_offset() {
+        assert_eq!(Ok(12), solve_captcha_with_offset("123123", 3));
+        assert_eq!(Ok(0), solve_captcha_with_offset("123123", 2));
+        assert_eq!(Ok(12), solve_captcha_with_offset("123123", 6));
+    }
+
+    #[test]
+    fn explain_matches() {
+        assert_eq!(Ok(vec![0, 2]), matching_positions("1122", 1));
+        assert_eq!(Ok(vec![7]), matching_positions("91212129", 1));
+        assert_eq!(Ok(vec![0, 2, 4, 6]), matching_positions("12131415", 4));
+    }
+
+    #[test]
+    fn empty_captcha() {
+        assert_eq!(Ok(0), solve_captcha(""));
+        assert_eq!(Ok(Vec::new()), matching_positions("", 1));
+    }
+
+    #[test]
+    fn reject_non_digits() {
+        let error = solve_captcha("12a4").unwrap_err();
+
+        assert_eq!(2..3, error.span());
+        assert_eq!("expected a digit, found `a`", error.message());
+    }
+
+    #[test]
+    fn reject_multi_byte_characters() {
+        let error = solve_second_captcha("12é4").unwrap_err();
+
+        assert_eq!("é", error.found());
+    }
+
+    #[test]
+    fn large_captcha() {
+        let captcha = "9".repeat(1_000_000);
+
+        assert_eq!(Ok(9_000_000), solve_second_captcha(&captcha));
+    }
+
+    #[test]
+    fn stream_matches_in_memory_solvers() {
+        for captcha in &[
+            "1122", "1111", "1234", "91212129", "123425", "12131415", "7", "",
+        ] {
+            assert_eq!(
+                solve_captcha(captcha).unwrap(),
+                stream_captcha(captcha.as_bytes()).unwrap()
+            );
+            assert_eq!(
+                solve_second_captcha(captcha).unwrap(),
+                stream_second_captcha(captcha.as_bytes()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn stream_across_chunks() {
+        let captcha = "3".repeat(CHUNK_SIZE * 3 + 7) + "\n";
+
+        assert_eq!(
+            3 * (CHUNK_SIZE as u64 * 3 + 7),
+            stream_captcha(captcha.as_bytes()).unwrap()
+        );
+        assert_eq!(
+            3 * (CHUNK_SIZE as u64 * 3 + 7),
+            stream_second_captcha(captcha.as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn stream_rejects_non_digits() {
+        match stream_captcha(&b"123x4\n"[..]) {
+            Err(StreamError::NotADigit { offset, byte }) => assert_eq!((3, b'x'), (offset, byte)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn stream_rejects_interior_whitespace() {
+        assert!(solve_captcha("1 2 1 2").is_err());
+
+        match stream_captcha(&b"1 2 1 2"[..]) {
+            Err(StreamError::NotADigit { offset, byte }) => assert_eq!((1, b' '), (offset, byte)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert!(stream_second_captcha(&b"\n1212"[..]).is_err());
+        assert_eq!(6, stream_second_captcha(&b"1212\r\n \n"[..]).unwrap());
     }
 }
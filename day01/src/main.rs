@@ -1,25 +1,50 @@
 extern crate day01;
+extern crate input;
 
-use std::fs::File;
-use std::io::prelude::*;
+use std::env;
+use std::io;
+use std::process;
 
 use day01::*;
+use input::Input;
+
+fn stream(solve: fn(io::Stdin) -> Result<u64, StreamError>) -> u64 {
+    solve(io::stdin()).unwrap_or_else(|e| {
+        eprintln!("<stdin>: {}", e);
+        process::exit(1);
+    })
+}
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
+    // Stdin can only be read once, so `day01 - [1|2]` streams a single part from it.
+    let args = env::args().skip(1).collect::<Vec<_>>();
 
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
+    if args.first().map(String::as_str) == Some("-") {
+        match args.get(1).map(String::as_str).unwrap_or("1") {
+            "1" => println!("First captcha solution: {}", stream(stream_captcha)),
+            "2" => println!("Second captcha solution: {}", stream(stream_second_captcha)),
+            part => {
+                eprintln!("no captcha part {}", part);
+                process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let (number, captcha) = input
+        .lines()
+        .find(|&(_, l)| !l.trim().is_empty())
+        .unwrap_or((1, ""));
 
     println!(
         "First captcha solution: {}",
-        solve_captcha(&contents.trim())
+        solve_captcha(captcha.trim()).unwrap_or_else(|e| input.syntax(number, e).exit())
     );
 
     println!(
         "Second captcha solution: {}",
-        solve_second_captcha(&contents.trim())
+        solve_second_captcha(captcha.trim()).unwrap_or_else(|e| input.syntax(number, e).exit())
     );
 }
@@ -1,27 +1,297 @@
-pub fn calculate_checksum(rows: &[Vec<u32>]) -> u32 {
-    rows.iter()
-        .map(|row| {
-            row.iter().max().unwrap_or(&0) - row.iter().min().unwrap_or(&0)
-        })
-        .sum()
-}
-
-pub fn calculate_second_checksum(rows: &[Vec<u32>]) -> u32 {
-    rows.iter()
-        .map(|row| {
-            for (index, i) in row.iter().enumerate() {
-                for j in row.as_slice()[(index + 1)..].iter() {
-                    if i % j == 0 {
-                        return i / j;
-                    } else if j % i == 0 {
-                        return j / i;
+extern crate input;
+
+use std::error;
+use std::fmt;
+
+use input::{Input, Mode, ParseError, Tokens};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RowError {
+    Zero { column: usize },
+    NoDivisiblePair,
+    MultipleDivisiblePairs((u64, u64), (u64, u64)),
+    Overflow,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RowError::Zero { column } => write!(f, "the value in column {} is zero", column),
+            RowError::NoDivisiblePair => write!(f, "no two values divide each other evenly"),
+            RowError::MultipleDivisiblePairs((a, b), (c, d)) => {
+                write!(
+                    f,
+                    "more than one pair divides evenly, e.g. {}/{} and {}/{}",
+                    a, b, c, d
+                )
+            }
+            RowError::Overflow => write!(f, "the checksum does not fit in 64 bits"),
+        }
+    }
+}
+
+impl error::Error for RowError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChecksumError {
+    pub row: usize,
+    pub error: RowError,
+}
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "row {}: {}", self.row, self.error)
+    }
+}
+
+impl error::Error for ChecksumError {}
+
+pub trait RowChecksum {
+    fn row_checksum(&self, row: &[u64]) -> Result<u64, RowError>;
+}
+
+// The difference between the largest and the smallest value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Spread;
+
+// The quotient of the only two values in the row that divide each other evenly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EvenDivision;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sum;
+
+// How far the value furthest from the median strays from it, using the lower median for
+// rows of even length.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MedianSpread;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GreatestCommonDivisor;
+
+impl RowChecksum for Spread {
+    fn row_checksum(&self, row: &[u64]) -> Result<u64, RowError> {
+        Ok(row.iter().max().unwrap_or(&0) - row.iter().min().unwrap_or(&0))
+    }
+}
+
+fn occurrences(sorted: &[u64], value: u64) -> usize {
+    sorted.partition_point(|&v| v <= value) - sorted.partition_point(|&v| v < value)
+}
+
+impl EvenDivision {
+    // With the row sorted, only smaller values can divide a value. Those are either
+    // scanned directly, or looked up as the divisors of the value when there are fewer of
+    // those to try, so long rows of large values stay well below quadratic.
+    pub fn divisible_pair(row: &[u64]) -> Result<(u64, u64), RowError> {
+        if let Some(column) = row.iter().position(|&v| v == 0) {
+            return Err(RowError::Zero { column: column + 1 });
+        }
+
+        let mut sorted = row.to_vec();
+        sorted.sort_unstable();
+
+        let mut pairs = Vec::new();
+
+        for (index, &value) in sorted.iter().enumerate() {
+            let smaller = &sorted[..index];
+            let root = value.isqrt();
+
+            if smaller.len() as u64 <= root {
+                for &divisor in smaller.iter().filter(|&&d| value % d == 0) {
+                    pairs.push((value, divisor));
+                }
+            } else {
+                for divisor in (1..=root).filter(|d| value % d == 0) {
+                    let other = value / divisor;
+
+                    for _ in 0..occurrences(smaller, divisor) {
+                        pairs.push((value, divisor));
+                    }
+
+                    if other != divisor {
+                        for _ in 0..occurrences(smaller, other) {
+                            pairs.push((value, other));
+                        }
                     }
                 }
             }
 
-            0
-        })
-        .sum()
+            if pairs.len() > 1 {
+                return Err(RowError::MultipleDivisiblePairs(pairs[0], pairs[1]));
+            }
+        }
+
+        pairs.pop().ok_or(RowError::NoDivisiblePair)
+    }
+}
+
+impl RowChecksum for EvenDivision {
+    fn row_checksum(&self, row: &[u64]) -> Result<u64, RowError> {
+        EvenDivision::divisible_pair(row).map(|(dividend, divisor)| dividend / divisor)
+    }
+}
+
+impl RowChecksum for Sum {
+    fn row_checksum(&self, row: &[u64]) -> Result<u64, RowError> {
+        row.iter()
+            .try_fold(0u64, |sum, &value| sum.checked_add(value))
+            .ok_or(RowError::Overflow)
+    }
+}
+
+impl RowChecksum for MedianSpread {
+    fn row_checksum(&self, row: &[u64]) -> Result<u64, RowError> {
+        if row.is_empty() {
+            return Ok(0);
+        }
+
+        let mut sorted = row.to_vec();
+        sorted.sort_unstable();
+
+        let median = sorted[(sorted.len() - 1) / 2];
+
+        Ok((sorted[sorted.len() - 1] - median).max(median - sorted[0]))
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl RowChecksum for GreatestCommonDivisor {
+    fn row_checksum(&self, row: &[u64]) -> Result<u64, RowError> {
+        Ok(row.iter().fold(0, |acc, &v| gcd(acc, v)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Separator {
+    Whitespace,
+    Tab,
+    Comma,
+}
+
+impl Separator {
+    pub fn detect(contents: &str) -> Self {
+        if contents.contains(',') {
+            Separator::Comma
+        } else if contents.contains('\t') {
+            Separator::Tab
+        } else {
+            Separator::Whitespace
+        }
+    }
+
+    fn cells<'a>(&self, line: &'a str) -> Vec<(&'a str, usize)> {
+        let separator = match *self {
+            Separator::Whitespace => {
+                return Tokens::new(line).map(|t| (t.text, t.span.start)).collect();
+            }
+            Separator::Tab => '\t',
+            Separator::Comma => ',',
+        };
+
+        let mut cells = Vec::new();
+        let mut offset = 0;
+
+        for cell in line.split(separator) {
+            cells.push((cell.trim(), offset + cell.len() - cell.trim_start().len()));
+            offset += cell.len() + separator.len_utf8();
+        }
+
+        cells
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Spreadsheet {
+    rows: Vec<Vec<u64>>,
+}
+
+impl Spreadsheet {
+    pub fn new(rows: Vec<Vec<u64>>) -> Self {
+        Spreadsheet { rows }
+    }
+
+    pub fn parse(input: &Input) -> Result<Self, input::Error> {
+        Spreadsheet::parse_with(input, Separator::detect(input.contents()))
+    }
+
+    pub fn parse_with(input: &Input, separator: Separator) -> Result<Self, input::Error> {
+        let mut rows = Vec::new();
+
+        for (number, l) in input.lines().filter(|&(_, l)| !l.trim().is_empty()) {
+            let mut row = Vec::new();
+
+            for (column, (cell, offset)) in separator.cells(l).into_iter().enumerate() {
+                match cell.parse() {
+                    Ok(value) => row.push(value),
+                    Err(_) if input.mode() == Mode::Lenient => row.push(0),
+                    Err(_) => {
+                        let expected = format!("a number in column {}", column + 1);
+                        let span = offset..(offset + cell.len());
+                        let mut error = ParseError::new(l, span, &expected);
+
+                        if cell.is_empty() {
+                            error = error.found_instead("an empty cell");
+                        }
+
+                        return Err(input.syntax(number, error));
+                    }
+                }
+            }
+
+            rows.push(row);
+        }
+
+        Ok(Spreadsheet { rows })
+    }
+
+    pub fn rows(&self) -> &[Vec<u64>] {
+        &self.rows
+    }
+
+    pub fn contributions<C: RowChecksum + ?Sized>(
+        &self,
+        strategy: &C,
+    ) -> Vec<Result<u64, RowError>> {
+        self.rows
+            .iter()
+            .map(|row| strategy.row_checksum(row))
+            .collect()
+    }
+
+    pub fn checksum<C: RowChecksum + ?Sized>(&self, strategy: &C) -> Result<u64, ChecksumError> {
+        checksum(&self.rows, strategy)
+    }
+}
+
+fn checksum<C: RowChecksum + ?Sized>(
+    rows: &[Vec<u64>],
+    strategy: &C,
+) -> Result<u64, ChecksumError> {
+    rows.iter().enumerate().try_fold(0u64, |sum, (index, row)| {
+        strategy
+            .row_checksum(row)
+            .and_then(|contribution| sum.checked_add(contribution).ok_or(RowError::Overflow))
+            .map_err(|error| ChecksumError {
+                row: index + 1,
+                error,
+            })
+    })
+}
+
+pub fn calculate_checksum(rows: &[Vec<u64>]) -> Result<u64, ChecksumError> {
+    checksum(rows, &Spread)
+}
+
+pub fn calculate_second_checksum(rows: &[Vec<u64>]) -> Result<u64, ChecksumError> {
+    checksum(rows, &EvenDivision)
 }
 
 #[cfg(test)]
@@ -30,43 +300,224 @@ mod tests {
 
     #[test]
     fn first_row() {
-        assert_eq!(8, calculate_checksum(&vec![vec![5, 1, 9, 5]]));
+        assert_eq!(Ok(8), calculate_checksum(&[vec![5, 1, 9, 5]]));
     }
 
     #[test]
     fn two_rows() {
         assert_eq!(
-            12,
-            calculate_checksum(&vec![vec![5, 1, 9, 5], vec![7, 5, 3]])
+            Ok(12),
+            calculate_checksum(&[vec![5, 1, 9, 5], vec![7, 5, 3]])
         );
     }
 
     #[test]
     fn three_rows() {
         assert_eq!(
-            18,
-            calculate_checksum(&vec![vec![5, 1, 9, 5], vec![7, 5, 3], vec![2, 4, 6, 8]])
+            Ok(18),
+            calculate_checksum(&[vec![5, 1, 9, 5], vec![7, 5, 3], vec![2, 4, 6, 8]])
         );
     }
 
     #[test]
     fn second_checksum_first_row() {
-        assert_eq!(4, calculate_second_checksum(&vec![vec![5, 9, 2, 8]]));
+        assert_eq!(Ok(4), calculate_second_checksum(&[vec![5, 9, 2, 8]]));
     }
 
     #[test]
     fn second_checksum_two_rows() {
         assert_eq!(
-            7,
-            calculate_second_checksum(&vec![vec![5, 9, 2, 8], vec![9, 4, 7, 3]])
+            Ok(7),
+            calculate_second_checksum(&[vec![5, 9, 2, 8], vec![9, 4, 7, 3]])
         );
     }
 
     #[test]
     fn second_checksum_three_rows() {
         assert_eq!(
-            9,
-            calculate_second_checksum(&vec![vec![5, 9, 2, 8], vec![9, 4, 7, 3], vec![3, 8, 6, 5]])
+            Ok(9),
+            calculate_second_checksum(&[vec![5, 9, 2, 8], vec![9, 4, 7, 3], vec![3, 8, 6, 5]])
+        );
+    }
+
+    #[test]
+    fn other_strategies() {
+        let row = [5, 9, 2, 8, 6];
+
+        assert_eq!(Ok(30), Sum.row_checksum(&row));
+        assert_eq!(Ok(4), MedianSpread.row_checksum(&row));
+        assert_eq!(Ok(1), GreatestCommonDivisor.row_checksum(&row));
+        assert_eq!(Ok(3), GreatestCommonDivisor.row_checksum(&[6, 9, 12]));
+        assert_eq!(Ok(0), MedianSpread.row_checksum(&[]));
+    }
+
+    #[test]
+    fn report_overflow() {
+        assert_eq!(Err(RowError::Overflow), Sum.row_checksum(&[u64::MAX, 1]));
+
+        let spreadsheet = Spreadsheet::new(vec![vec![1, 2], vec![u64::MAX - 1], vec![3]]);
+        let error = spreadsheet.checksum(&Sum).unwrap_err();
+
+        assert_eq!(2, error.row);
+        assert_eq!(
+            "row 2: the checksum does not fit in 64 bits",
+            error.to_string()
+        );
+        assert_eq!(
+            Ok(u64::MAX),
+            Spreadsheet::new(vec![vec![u64::MAX]]).checksum(&Sum)
+        );
+    }
+
+    #[test]
+    fn report_contributions() {
+        let spreadsheet =
+            Spreadsheet::new(vec![vec![5, 9, 2, 8], vec![9, 4, 7, 3], vec![3, 8, 6, 5]]);
+
+        assert_eq!(
+            vec![Ok(7), Ok(6), Ok(5)],
+            spreadsheet.contributions(&Spread)
+        );
+        assert_eq!(
+            vec![Ok(4), Ok(3), Ok(2)],
+            spreadsheet.contributions(&EvenDivision)
+        );
+        assert_eq!(Ok(9), spreadsheet.checksum(&EvenDivision));
+    }
+
+    #[test]
+    fn use_strategy_objects() {
+        let spreadsheet = Spreadsheet::new(vec![vec![5, 1, 9, 5], vec![7, 5, 3]]);
+        let strategies: Vec<Box<dyn RowChecksum>> = vec![Box::new(Spread), Box::new(Sum)];
+
+        let checksums = strategies
+            .iter()
+            .map(|s| spreadsheet.checksum(s.as_ref()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![12, 35], checksums);
+    }
+
+    fn load(contents: &str) -> Result<Spreadsheet, input::Error> {
+        Spreadsheet::parse(&Input::embedded("input.txt", contents))
+    }
+
+    #[test]
+    fn detect_separator() {
+        assert_eq!(Separator::Comma, Separator::detect("1,2\n3,4\n"));
+        assert_eq!(Separator::Tab, Separator::detect("1\t2\n"));
+        assert_eq!(Separator::Whitespace, Separator::detect("1  2\n"));
+    }
+
+    #[test]
+    fn load_any_separator() {
+        let expected = Spreadsheet::new(vec![vec![5, 1, 9, 5], vec![7, 5, 3]]);
+
+        assert_eq!(expected, load("5 1  9 5\n\n7 5 3\n").unwrap());
+        assert_eq!(expected, load("5\t1\t9\t5\n7\t5\t3\r\n").unwrap());
+        assert_eq!(expected, load("5, 1, 9, 5\n7,5,3\n").unwrap());
+    }
+
+    #[test]
+    fn load_large_values() {
+        let spreadsheet = load("1\t10000000000\n").unwrap();
+
+        assert_eq!(Ok(9_999_999_999), spreadsheet.checksum(&Spread));
+    }
+
+    #[test]
+    fn report_invalid_cell() {
+        assert_eq!(
+            [
+                "error: expected a number in column 3, found `x9`",
+                " --> input.txt:2:6",
+                "  |",
+                "2 | 7,5, x9",
+                "  |      ^^ expected a number in column 3",
+            ]
+            .join("\n"),
+            load("5,1\n7,5, x9\n").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn report_empty_cell() {
+        assert_eq!(
+            [
+                "error: expected a number in column 2, found an empty cell",
+                " --> input.txt:1:3",
+                "  |",
+                "1 | 5\t\t1",
+                "  |   ^ expected a number in column 2",
+            ]
+            .join("\n"),
+            load("5\t\t1\n").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn lenient_cells() {
+        let input = Input::embedded("input.txt", "5 x 9\n").with_mode(Mode::Lenient);
+
+        assert_eq!(
+            vec![vec![5, 0, 9]],
+            Spreadsheet::parse(&input).unwrap().rows()
+        );
+    }
+
+    #[test]
+    fn flag_rows_without_a_single_pair() {
+        assert_eq!(
+            Err(RowError::Zero { column: 3 }),
+            EvenDivision.row_checksum(&[5, 9, 0, 8])
+        );
+        assert_eq!(
+            Err(RowError::NoDivisiblePair),
+            EvenDivision.row_checksum(&[5, 7, 9])
+        );
+        assert_eq!(
+            Err(RowError::NoDivisiblePair),
+            EvenDivision.row_checksum(&[])
+        );
+        assert_eq!(
+            Err(RowError::MultipleDivisiblePairs((4, 2), (8, 2))),
+            EvenDivision.row_checksum(&[8, 3, 2, 4])
+        );
+        assert_eq!(Ok(1), EvenDivision.row_checksum(&[7, 5, 7]));
+        assert_eq!(
+            Err(RowError::MultipleDivisiblePairs((7, 7), (7, 7))),
+            EvenDivision.row_checksum(&[7, 7, 7])
+        );
+    }
+
+    #[test]
+    fn report_failing_row() {
+        let spreadsheet =
+            Spreadsheet::new(vec![vec![5, 9, 2, 8], vec![9, 4, 7, 3], vec![3, 0, 6, 5]]);
+
+        assert_eq!(
+            "row 3: the value in column 2 is zero",
+            spreadsheet.checksum(&EvenDivision).unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(RowError::Zero { column: 2 }),
+            spreadsheet.contributions(&EvenDivision)[2]
+        );
+    }
+
+    #[test]
+    fn long_row_of_large_values() {
+        let mut row = (1_000_001..)
+            .step_by(2)
+            .filter(|&v: &u64| (3..=v.isqrt()).step_by(2).all(|d| v % d != 0))
+            .take(3000)
+            .collect::<Vec<_>>();
+
+        row.push(row[1234] * 2);
+
+        assert_eq!(
+            Ok((row[1234] * 2, row[1234])),
+            EvenDivision::divisible_pair(&row)
         );
     }
 }
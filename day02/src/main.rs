@@ -1,24 +1,22 @@
 extern crate day02;
+extern crate input;
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::process;
 
 use day02::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-    let mut rows: Vec<Vec<u32>> = Vec::new();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let spreadsheet = Spreadsheet::parse(&input).unwrap_or_else(|e| e.exit());
 
-    for line in file.lines() {
-        let line = line.expect("error reading file");
+    let checksum = |strategy: &dyn RowChecksum| {
+        spreadsheet.checksum(strategy).unwrap_or_else(|e| {
+            eprintln!("{}: {}", input.name(), e);
+            process::exit(1);
+        })
+    };
 
-        rows.push(
-            line.split_whitespace()
-                .map(|i| i.parse().unwrap_or(0))
-                .collect(),
-        );
-    }
-
-    println!("Checksum: {}", calculate_checksum(&rows));
-    println!("Second checksum: {}", calculate_second_checksum(&rows));
+    println!("Checksum: {}", checksum(&Spread));
+    println!("Second checksum: {}", checksum(&EvenDivision));
 }
@@ -1,98 +1,81 @@
-use std::collections::HashMap;
+use std::convert::TryFrom;
 
-struct SpiralPattern {
-    level: u32,
-    index: u32,
-    position: (i32, i32),
-}
+mod render;
+mod spiral;
+mod stress;
+
+pub use render::{manhattan_path, render_indices, render_values, RenderError, MAX_RINGS};
+pub use spiral::{Direction, Orientation, Shape, Spiral, SpiralPattern};
+pub use stress::{Combine, Neighbourhood, Overflow, StressTest, Value};
 
-struct SpiralPatternWithValues {
-    pattern: SpiralPattern,
-    values: HashMap<(i32, i32), u32>,
+fn spiral_pattern() -> SpiralPattern {
+    Spiral::default().walk()
 }
 
-impl SpiralPattern {
-    fn with_values(self) -> SpiralPatternWithValues {
-        SpiralPatternWithValues {
-            pattern: self,
-            values: HashMap::new(),
-        }
+// Ring `k` holds the squares after `(2k - 1)^2` up to `(2k + 1)^2`, as four sides of `2k`
+// squares each. In the coordinates of the default spiral, the walk around the ring starts at
+// `(k, k - 1)` and heads towards `(k, -k)` first.
+//
+// Squares are numbered from 1, and the outermost ring is only partly addressable with `i32`
+// coordinates, so both conversions return `None` outside of that domain.
+pub fn spiral_position(square: u64) -> Option<(i32, i32)> {
+    if square == 0 {
+        return None;
     }
-}
 
-impl Iterator for SpiralPattern {
-    type Item = (i32, i32);
-
-    fn next(&mut self) -> Option<(i32, i32)> {
-        self.position = if self.level == 0 && self.index == 0 {
-            (0, 0)
-        } else if self.index == 0 {
-            (self.position.0 + 1, self.position.1)
-        } else if self.index / (self.level * 2) == 0 {
-            (self.position.0, self.position.1 - 1)
-        } else if self.index / (self.level * 2) == 1 {
-            (self.position.0 - 1, self.position.1)
-        } else if self.index / (self.level * 2) == 2 {
-            (self.position.0, self.position.1 + 1)
-        } else if self.index / (self.level * 2) == 3 {
-            (self.position.0 + 1, self.position.1)
-        } else {
-            self.position
-        };
-
-        self.index += 1;
-
-        if self.index >= 8 * self.level {
-            self.level += 1;
-            self.index = 0;
-        }
+    let ring = (square - 1).isqrt().div_ceil(2);
 
-        Some(self.position)
+    if ring == 0 {
+        return Some((0, 0));
     }
-}
-
-impl Iterator for SpiralPatternWithValues {
-    type Item = u32;
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
-    fn next(&mut self) -> Option<u32> {
-        let position = self.pattern.next().unwrap();
+    let k = ring as i64;
+    let t = (square - (2 * ring - 1) * (2 * ring - 1) - 1) as i64;
 
-        let mut value = self.values.get(&(position.0 - 1, position.1 - 1)).unwrap_or(&0)
-            + self.values.get(&(position.0, position.1 - 1)).unwrap_or(&0)
-            + self.values.get(&(position.0 + 1, position.1 - 1)).unwrap_or(&0)
-            + self.values.get(&(position.0 - 1, position.1)).unwrap_or(&0)
-            + self.values.get(&(position.0 + 1, position.1)).unwrap_or(&0)
-            + self.values.get(&(position.0 - 1, position.1 + 1)).unwrap_or(&0)
-            + self.values.get(&(position.0, position.1 + 1)).unwrap_or(&0)
-            + self.values.get(&(position.0 + 1, position.1 + 1)).unwrap_or(&0);
+    let (x, y) = match t / (2 * k) {
+        0 => (k, k - 1 - t),
+        1 => (k - 1 - (t - 2 * k), -k),
+        2 => (-k, -k + 1 + (t - 4 * k)),
+        _ => (-k + 1 + (t - 6 * k), k),
+    };
 
-        if position.0 == 0 && position.1 == 0 {
-            value = 1;
-        }
+    Some((i32::try_from(x).ok()?, i32::try_from(y).ok()?))
+}
 
-        self.values.insert(position, value);
+pub fn spiral_index(position: (i32, i32)) -> Option<u64> {
+    let (x, y) = (i64::from(position.0), i64::from(position.1));
+    let k = x.abs().max(y.abs());
 
-        Some(value)
+    if k == 0 {
+        return Some(1);
     }
-}
 
-fn spiral_pattern() -> SpiralPattern {
-    SpiralPattern {
-        level: 0,
-        index: 0,
-        position: (0, 0),
-    }
+    let t = if x == k && y < k {
+        k - 1 - y
+    } else if y == -k {
+        2 * k + (k - 1 - x)
+    } else if x == -k {
+        4 * k + (y + k - 1)
+    } else {
+        6 * k + (x + k - 1)
+    };
+
+    let inner = (2 * k - 1) as u64;
+
+    inner.checked_mul(inner)?.checked_add(1 + t as u64)
 }
 
-pub fn distance(i: u32) -> u32 {
-    let position = spiral_pattern().nth(i as usize - 1).unwrap();
+pub fn distance(square: u64) -> Option<u64> {
+    let (x, y) = spiral_position(square)?;
 
-    (position.0.abs() + position.1.abs()) as u32
+    Some((i64::from(x).abs() + i64::from(y).abs()) as u64)
 }
 
-pub fn value_greater_than(v: u32) -> u32 {
-    spiral_pattern().with_values().find(|&i| i > v).unwrap()
+pub fn value_greater_than(v: u64) -> Result<u64, Overflow> {
+    spiral_pattern()
+        .stress_test(Neighbourhood::Eight, Combine::Sum)
+        .find(|value| value.as_ref().map_or(true, |&value| value > v))
+        .expect("stress tests only end after an overflow")
 }
 
 #[cfg(test)]
@@ -101,41 +84,107 @@ mod tests {
 
     #[test]
     fn distance_for_1() {
-        assert_eq!(0, distance(1));
+        assert_eq!(Some(0), distance(1));
     }
 
     #[test]
     fn distance_for_12() {
-        assert_eq!(3, distance(12));
+        assert_eq!(Some(3), distance(12));
     }
 
     #[test]
     fn distance_for_23() {
-        assert_eq!(2, distance(23));
+        assert_eq!(Some(2), distance(23));
     }
 
     #[test]
     fn distance_for_1024() {
-        assert_eq!(31, distance(1024));
+        assert_eq!(Some(31), distance(1024));
+    }
+
+    #[test]
+    fn distance_far_out() {
+        assert_eq!(Some(2_000_000_000), distance(4_000_000_004_000_000_001));
+    }
+
+    #[test]
+    fn closed_form_matches_iterator() {
+        for (square, position) in (1..).zip(spiral_pattern()).take(10_000) {
+            assert_eq!(Some(position), spiral_position(square), "square {}", square);
+            assert_eq!(Some(square), spiral_index(position));
+        }
+    }
+
+    #[test]
+    fn round_trip_large_squares() {
+        for &square in &[
+            1u64 << 40,
+            999_999_999_999,
+            (1 << 62) + 12_345,
+            u64::from(u32::MAX) * 4,
+        ] {
+            assert_eq!(Some(square), spiral_position(square).and_then(spiral_index));
+        }
+    }
+
+    #[test]
+    fn no_square_zero() {
+        assert_eq!(None, spiral_position(0));
+        assert_eq!(None, distance(0));
+    }
+
+    // Ring 2^31 reaches `i32::MIN` on two of its sides, but `i32::MAX` is one short of its
+    // other two, and its last squares no longer fit a `u64` index.
+    #[test]
+    fn boundary_ring() {
+        let corner = u64::MAX - (1 << 32) + 3;
+
+        assert_eq!(Some(corner), spiral_index((i32::MAX, i32::MIN)));
+        assert_eq!(Some((i32::MAX, i32::MIN)), spiral_position(corner));
+        assert_eq!(Some((i32::MIN + 2, i32::MIN)), spiral_position(u64::MAX));
+        assert_eq!(Some(u64::MAX), spiral_index((i32::MIN + 2, i32::MIN)));
+        assert_eq!(Some(u64::from(u32::MAX) - 1), distance(u64::MAX));
+
+        assert_eq!(None, spiral_index((i32::MIN, 0)));
+        assert_eq!(None, spiral_index((i32::MIN, i32::MIN)));
+        assert_eq!(None, spiral_position(corner - 1));
     }
 
     #[test]
     fn greater_than_1() {
-        assert_eq!(2, value_greater_than(1));
+        assert_eq!(Ok(2), value_greater_than(1));
     }
 
     #[test]
     fn greater_than_5() {
-        assert_eq!(10, value_greater_than(5));
+        assert_eq!(Ok(10), value_greater_than(5));
     }
 
     #[test]
     fn greater_than_130() {
-        assert_eq!(133, value_greater_than(130));
+        assert_eq!(Ok(133), value_greater_than(130));
     }
 
     #[test]
     fn greater_than_780() {
-        assert_eq!(806, value_greater_than(780));
+        assert_eq!(Ok(806), value_greater_than(780));
+    }
+
+    #[test]
+    fn greater_than_what_fits() {
+        assert!(value_greater_than(u64::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn values_on_hexagonal_spiral() {
+        assert_eq!(
+            vec![1, 1, 2, 3, 4, 5, 7, 8],
+            Spiral::hexagonal()
+                .walk()
+                .stress_test(Neighbourhood::Hexagonal, Combine::Sum)
+                .take(8)
+                .collect::<Result<Vec<u32>, _>>()
+                .unwrap()
+        );
     }
 }
@@ -1,10 +1,65 @@
 extern crate day03;
 
+use std::env;
+use std::process;
+
 use day03::*;
 
-const INPUT: u32 = 361527;
+const INPUT: u64 = 361527;
+
+fn usage() -> ! {
+    eprintln!("usage: day03 [render <rings> [<square>] [--values]]");
+    process::exit(1);
+}
+
+// `day03 render <rings> [<square>] [--values]` draws the spiral instead of solving it.
+fn render(args: &[String]) {
+    let values = args.iter().any(|a| a == "--values");
+    let numbers = args.iter().filter(|a| *a != "--values").collect::<Vec<_>>();
+
+    let rings = numbers
+        .first()
+        .and_then(|r| r.parse().ok())
+        .unwrap_or_else(|| usage());
+    let highlight = numbers
+        .get(1)
+        .map(|square| square.parse().ok().unwrap_or_else(|| usage()));
+
+    if numbers.len() > 2 {
+        usage();
+    }
+
+    let rendered = if values {
+        render_values(rings, highlight)
+    } else {
+        render_indices(rings, highlight)
+    };
+
+    match rendered {
+        Ok(rendered) => print!("{}", rendered),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
 
 fn main() {
-    println!("Distance: {}", distance(INPUT));
-    println!("Greater value: {}", value_greater_than(INPUT));
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    match args.first().map(String::as_str) {
+        Some("render") => return render(&args[1..]),
+        Some(_) => usage(),
+        None => {}
+    }
+
+    match distance(INPUT) {
+        Some(distance) => println!("Distance: {}", distance),
+        None => eprintln!("square {} is not on the spiral", INPUT),
+    }
+
+    match value_greater_than(INPUT) {
+        Ok(value) => println!("Greater value: {}", value),
+        Err(e) => eprintln!("{}", e),
+    }
 }
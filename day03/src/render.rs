@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::error;
+use std::fmt;
+
+use stress::{Combine, Neighbourhood, Overflow};
+use {spiral_index, spiral_pattern, spiral_position};
+
+// One of the shortest routes data can take to the access port: along the row first, then
+// along the column.
+pub fn manhattan_path(square: u64) -> Option<Vec<(i32, i32)>> {
+    let (x, y) = spiral_position(square)?;
+
+    let row = (0..=x.abs()).map(|i| (x - i * x.signum(), y));
+    let column = (1..=y.abs()).map(|i| (0, y - i * y.signum()));
+
+    Some(row.chain(column).collect())
+}
+
+// Every square of every ring is rendered, so the number of rings is kept to what still
+// makes sense as text.
+pub const MAX_RINGS: u32 = 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RenderError {
+    TooManyRings(u32),
+    NotOnSpiral(u64),
+    Overflow(Overflow),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RenderError::TooManyRings(rings) => {
+                write!(
+                    f,
+                    "cannot render {} rings, at most {} fit",
+                    rings, MAX_RINGS
+                )
+            }
+            RenderError::NotOnSpiral(square) => write!(f, "square {} is not on the spiral", square),
+            RenderError::Overflow(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for RenderError {}
+
+impl From<Overflow> for RenderError {
+    fn from(e: Overflow) -> Self {
+        RenderError::Overflow(e)
+    }
+}
+
+fn check_rings(rings: u32) -> Result<i32, RenderError> {
+    if rings > MAX_RINGS {
+        return Err(RenderError::TooManyRings(rings));
+    }
+
+    Ok(rings as i32)
+}
+
+fn render<F>(rings: i32, highlight: Option<u64>, label: F) -> Result<String, RenderError>
+where
+    F: Fn(u64) -> String,
+{
+    let path = match highlight {
+        Some(square) => manhattan_path(square).ok_or(RenderError::NotOnSpiral(square))?,
+        None => Vec::new(),
+    };
+    let path = path.into_iter().collect::<HashSet<_>>();
+
+    let index = |x, y| spiral_index((x, y)).expect("rendered rings have u64 indices");
+    let labels = (-rings..=rings)
+        .map(|y| {
+            (-rings..=rings)
+                .map(|x| label(index(x, y)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let width = labels
+        .iter()
+        .flat_map(|row| row.iter().map(String::len))
+        .max()
+        .unwrap_or(1);
+
+    let mut rendered = String::new();
+
+    for (y, row) in (-rings..=rings).zip(labels) {
+        let cells = (-rings..=rings).zip(row).map(|(x, label)| {
+            if path.contains(&(x, y)) {
+                format!("[{:>width$}]", label, width = width)
+            } else {
+                format!(" {:>width$} ", label, width = width)
+            }
+        });
+
+        rendered += cells.collect::<String>().trim_end();
+        rendered.push('\n');
+    }
+
+    Ok(rendered)
+}
+
+pub fn render_indices(rings: u32, highlight: Option<u64>) -> Result<String, RenderError> {
+    render(check_rings(rings)?, highlight, |square| square.to_string())
+}
+
+pub fn render_values(rings: u32, highlight: Option<u64>) -> Result<String, RenderError> {
+    let rings = check_rings(rings)?;
+    let squares = (2 * rings as usize + 1).pow(2);
+
+    let values = spiral_pattern()
+        .stress_test::<u64>(Neighbourhood::Eight, Combine::Sum)
+        .take(squares)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    render(rings, highlight, |square| {
+        values[square as usize - 1].to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_to_access_port() {
+        assert_eq!(Some(vec![(0, 0)]), manhattan_path(1));
+        assert_eq!(
+            Some(vec![(2, -1), (1, -1), (0, -1), (0, 0)]),
+            manhattan_path(12)
+        );
+        assert_eq!(Some(vec![(0, 2), (0, 1), (0, 0)]), manhattan_path(23));
+    }
+
+    #[test]
+    fn render_spiral_indices() {
+        assert_eq!(
+            [
+                " 17  16  15  14  13",
+                " 18   5 [ 4][ 3][12]",
+                " 19   6 [ 1]  2  11",
+                " 20   7   8   9  10",
+                " 21  22  23  24  25",
+                "",
+            ]
+            .join("\n"),
+            render_indices(2, Some(12)).unwrap()
+        );
+    }
+
+    #[test]
+    fn render_stress_test_values() {
+        assert_eq!(
+            ["  5   4   2", " 10 [ 1]  1", " 11  23  25", ""].join("\n"),
+            render_values(1, Some(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn render_highlight_outside_rings() {
+        assert_eq!(Ok("[1]\n".to_owned()), render_indices(0, Some(1000)));
+    }
+
+    #[test]
+    fn render_too_many_values() {
+        match render_values(40, None) {
+            Err(RenderError::Overflow(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reject_too_many_rings() {
+        assert_eq!(
+            Err(RenderError::TooManyRings(u32::MAX)),
+            render_indices(u32::MAX, None)
+        );
+        assert_eq!(
+            Err(RenderError::TooManyRings(MAX_RINGS + 1)),
+            render_values(MAX_RINGS + 1, None)
+        );
+    }
+
+    #[test]
+    fn reject_square_zero() {
+        assert_eq!(Err(RenderError::NotOnSpiral(0)), render_indices(2, Some(0)));
+        assert_eq!(Err(RenderError::NotOnSpiral(0)), render_values(1, Some(0)));
+    }
+}
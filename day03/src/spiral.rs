@@ -0,0 +1,330 @@
+// Square spirals use `(x, y)` with `y` growing towards the south, hexagonal ones use axial
+// `(q, r)` coordinates with `r` growing towards the south-east.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Square,
+    Hexagonal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    East,
+    NorthEast,
+    North,
+    NorthWest,
+    West,
+    SouthWest,
+    South,
+    SouthEast,
+}
+
+const SQUARE_DIRECTIONS: [Direction; 4] = [
+    Direction::East,
+    Direction::North,
+    Direction::West,
+    Direction::South,
+];
+
+const HEX_DIRECTIONS: [Direction; 6] = [
+    Direction::East,
+    Direction::NorthEast,
+    Direction::NorthWest,
+    Direction::West,
+    Direction::SouthWest,
+    Direction::SouthEast,
+];
+
+impl Shape {
+    // Every direction a spiral of this shape can step in, counter-clockwise from east.
+    pub fn directions(&self) -> &'static [Direction] {
+        match *self {
+            Shape::Square => &SQUARE_DIRECTIONS,
+            Shape::Hexagonal => &HEX_DIRECTIONS,
+        }
+    }
+
+    pub fn neighbours(&self) -> Vec<(i32, i32)> {
+        match *self {
+            Shape::Square => (-1..=1)
+                .flat_map(|y| (-1..=1).map(move |x| (x, y)))
+                .filter(|&offset| offset != (0, 0))
+                .collect(),
+            Shape::Hexagonal => HEX_DIRECTIONS
+                .iter()
+                .map(|d| d.offset(Shape::Hexagonal))
+                .collect(),
+        }
+    }
+
+    fn side_length(&self, ring: u32) -> u32 {
+        match *self {
+            Shape::Square => 2 * ring,
+            Shape::Hexagonal => ring,
+        }
+    }
+}
+
+impl Direction {
+    pub fn offset(&self, shape: Shape) -> (i32, i32) {
+        match (shape, *self) {
+            (_, Direction::East) => (1, 0),
+            (_, Direction::West) => (-1, 0),
+            (Shape::Square, Direction::North) => (0, -1),
+            (Shape::Square, Direction::South) => (0, 1),
+            (Shape::Hexagonal, Direction::NorthEast) => (1, -1),
+            (Shape::Hexagonal, Direction::NorthWest) => (0, -1),
+            (Shape::Hexagonal, Direction::SouthWest) => (-1, 1),
+            (Shape::Hexagonal, Direction::SouthEast) => (0, 1),
+            (shape, direction) => panic!("{:?} spirals cannot step {:?}", shape, direction),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spiral {
+    shape: Shape,
+    orientation: Orientation,
+    start: Direction,
+}
+
+impl Default for Spiral {
+    fn default() -> Self {
+        Self::square()
+    }
+}
+
+impl Spiral {
+    pub fn square() -> Self {
+        Spiral {
+            shape: Shape::Square,
+            orientation: Orientation::CounterClockwise,
+            start: Direction::East,
+        }
+    }
+
+    pub fn hexagonal() -> Self {
+        Spiral {
+            shape: Shape::Hexagonal,
+            ..Spiral::square()
+        }
+    }
+
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn starting(mut self, start: Direction) -> Self {
+        assert!(
+            self.shape.directions().contains(&start),
+            "{:?} spirals cannot start {:?}",
+            self.shape,
+            start
+        );
+
+        self.start = start;
+        self
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn start(&self) -> Direction {
+        self.start
+    }
+
+    pub fn walk(&self) -> SpiralPattern {
+        let all = self.shape.directions();
+        let first = all.iter().position(|&d| d == self.start).unwrap_or(0);
+
+        let directions = (0..all.len())
+            .map(|turns| match self.orientation {
+                Orientation::CounterClockwise => all[(first + turns) % all.len()],
+                Orientation::Clockwise => all[(first + all.len() - turns) % all.len()],
+            })
+            .map(|d| d.offset(self.shape))
+            .collect();
+
+        SpiralPattern {
+            shape: self.shape,
+            directions,
+            ring: 0,
+            side: all.len(),
+            remaining: 0,
+            position: (0, 0),
+        }
+    }
+}
+
+// Each ring is entered with a single step in the starting direction, followed by one side
+// that is a square shorter than the rest, as that step already covered its first square.
+// The remaining sides turn one direction further each, ending back in the starting one.
+#[derive(Clone, Debug)]
+pub struct SpiralPattern {
+    shape: Shape,
+    directions: Vec<(i32, i32)>,
+    ring: u32,
+    side: usize,
+    remaining: u32,
+    position: (i32, i32),
+}
+
+impl SpiralPattern {
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    fn advance(&mut self) {
+        while self.remaining == 0 {
+            self.side += 1;
+
+            if self.side > self.directions.len() {
+                self.ring += 1;
+                self.side = 0;
+            }
+
+            let length = self.shape.side_length(self.ring);
+
+            self.remaining = match self.side {
+                0 => 1,
+                1 => length - 1,
+                _ => length,
+            };
+        }
+
+        let step = self.directions[self.side % self.directions.len()];
+
+        self.position = (self.position.0 + step.0, self.position.1 + step.1);
+        self.remaining -= 1;
+    }
+}
+
+impl Iterator for SpiralPattern {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<(i32, i32)> {
+        let position = self.position;
+
+        self.advance();
+
+        Some(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn hex_distance(position: (i32, i32)) -> i32 {
+        (position.0.abs() + position.1.abs() + (position.0 + position.1).abs()) / 2
+    }
+
+    #[test]
+    fn default_square_spiral() {
+        assert_eq!(
+            vec![
+                (0, 0),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (2, 1)
+            ],
+            Spiral::default().walk().take(10).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn clockwise_square_spiral() {
+        let spiral = Spiral::square().with_orientation(Orientation::Clockwise);
+
+        assert_eq!(
+            vec![
+                (0, 0),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (2, -1)
+            ],
+            spiral.walk().take(10).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn square_spiral_starting_north() {
+        let spiral = Spiral::square().starting(Direction::North);
+
+        assert_eq!(
+            vec![(0, 0), (0, -1), (-1, -1), (-1, 0), (-1, 1)],
+            spiral.walk().take(5).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn hexagonal_spiral() {
+        assert_eq!(
+            vec![
+                (0, 0),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (2, 0)
+            ],
+            Spiral::hexagonal().walk().take(9).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn hexagonal_rings_are_complete() {
+        let cells = Spiral::hexagonal()
+            .with_orientation(Orientation::Clockwise)
+            .starting(Direction::SouthWest)
+            .walk()
+            .take(1 + 6 + 12 + 18)
+            .collect::<Vec<_>>();
+
+        assert_eq!(cells.len(), cells.iter().collect::<HashSet<_>>().len());
+        assert!(cells.iter().all(|&c| hex_distance(c) <= 3));
+        assert!(cells
+            .windows(2)
+            .all(|w| hex_distance((w[1].0 - w[0].0, w[1].1 - w[0].1)) == 1));
+    }
+
+    #[test]
+    fn neighbours_of_each_shape() {
+        assert_eq!(8, Shape::Square.neighbours().len());
+        assert_eq!(6, Shape::Hexagonal.neighbours().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot start NorthEast")]
+    fn square_spiral_cannot_start_diagonally() {
+        Spiral::square().starting(Direction::NorthEast);
+    }
+}
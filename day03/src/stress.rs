@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use spiral::{Shape, SpiralPattern};
+
+pub trait Value: Copy + Ord + fmt::Debug {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_value {
+    ($($t:ty),*) => {
+        $(
+            impl Value for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_value!(u32, u64, u128);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    Four,
+    Eight,
+    Hexagonal,
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<(i32, i32)> {
+        match *self {
+            Neighbourhood::Four => vec![(1, 0), (0, -1), (-1, 0), (0, 1)],
+            Neighbourhood::Eight => Shape::Square.neighbours(),
+            Neighbourhood::Hexagonal => Shape::Hexagonal.neighbours(),
+        }
+    }
+}
+
+// The modulus of a product is applied after every multiplication, so it only overflows
+// when the square of the modulus does not fit the value type. A zero modulus is rejected
+// when the stress test is set up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combine<T> {
+    Sum,
+    ProductModulo(T),
+    Max,
+}
+
+impl<T: Value> Combine<T> {
+    fn identity(&self) -> T {
+        match *self {
+            Combine::Sum | Combine::Max => T::zero(),
+            Combine::ProductModulo(_) => T::one(),
+        }
+    }
+
+    fn apply(&self, acc: T, value: T) -> Option<T> {
+        match *self {
+            Combine::Sum => acc.checked_add(value),
+            Combine::ProductModulo(modulus) => acc.checked_mul(value)?.checked_rem(modulus),
+            Combine::Max => Some(acc.max(value)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overflow {
+    pub square: u64,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the value of square {} does not fit its type",
+            self.square
+        )
+    }
+}
+
+impl error::Error for Overflow {}
+
+pub struct StressTest<T> {
+    pattern: SpiralPattern,
+    neighbours: Vec<(i32, i32)>,
+    combine: Combine<T>,
+    seed: T,
+    values: HashMap<(i32, i32), T>,
+    square: u64,
+    overflowed: bool,
+}
+
+impl<T: Value> StressTest<T> {
+    pub fn with_seed(mut self, seed: T) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl SpiralPattern {
+    pub fn stress_test<T: Value>(
+        self,
+        neighbourhood: Neighbourhood,
+        combine: Combine<T>,
+    ) -> StressTest<T> {
+        assert!(
+            combine != Combine::ProductModulo(T::zero()),
+            "products cannot be taken modulo zero"
+        );
+
+        StressTest {
+            pattern: self,
+            neighbours: neighbourhood.offsets(),
+            combine,
+            seed: T::one(),
+            values: HashMap::new(),
+            square: 0,
+            overflowed: false,
+        }
+    }
+}
+
+// Once a value overflows, every later one would depend on it, so the sequence ends there.
+impl<T: Value> Iterator for StressTest<T> {
+    type Item = Result<T, Overflow>;
+
+    fn next(&mut self) -> Option<Result<T, Overflow>> {
+        if self.overflowed {
+            return None;
+        }
+
+        let position = self.pattern.next()?;
+        self.square += 1;
+
+        let value = if self.square == 1 {
+            Some(self.seed)
+        } else {
+            self.neighbours
+                .iter()
+                .filter_map(|&(x, y)| self.values.get(&(position.0 + x, position.1 + y)))
+                .try_fold(self.combine.identity(), |acc, &v| {
+                    self.combine.apply(acc, v)
+                })
+        };
+
+        match value {
+            Some(value) => {
+                self.values.insert(position, value);
+                Some(Ok(value))
+            }
+            None => {
+                self.overflowed = true;
+                Some(Err(Overflow {
+                    square: self.square,
+                }))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spiral::Spiral;
+
+    fn values<T: Value>(neighbourhood: Neighbourhood, combine: Combine<T>, count: usize) -> Vec<T> {
+        Spiral::default()
+            .walk()
+            .stress_test(neighbourhood, combine)
+            .take(count)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn eight_connected_sums() {
+        assert_eq!(
+            vec![1, 1, 2, 4, 5, 10, 11, 23, 25, 26],
+            values::<u64>(Neighbourhood::Eight, Combine::Sum, 10)
+        );
+    }
+
+    #[test]
+    fn four_connected_sums() {
+        assert_eq!(
+            vec![1, 1, 1, 2, 2, 3, 3, 4, 5],
+            values::<u32>(Neighbourhood::Four, Combine::Sum, 9)
+        );
+    }
+
+    #[test]
+    fn maximum_of_neighbours() {
+        assert_eq!(Some(9), Combine::Max.apply(3u64, 9));
+        assert_eq!(Some(9), Combine::Max.apply(9u64, 3));
+
+        let maximums = Spiral::default()
+            .walk()
+            .stress_test(Neighbourhood::Four, Combine::Max)
+            .with_seed(7u32)
+            .take(50)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(maximums.iter().all(|&v| v == 7));
+    }
+
+    #[test]
+    fn product_modulo() {
+        let products = Spiral::default()
+            .walk()
+            .stress_test(Neighbourhood::Eight, Combine::ProductModulo(10u64))
+            .with_seed(3)
+            .take(4)
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_eq!(Ok(vec![3, 3, 9, 1]), products);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulo zero")]
+    fn reject_zero_modulus() {
+        Spiral::default()
+            .walk()
+            .stress_test(Neighbourhood::Eight, Combine::ProductModulo(0u64));
+    }
+
+    #[test]
+    fn stop_at_first_overflow() {
+        let narrow = Spiral::default()
+            .walk()
+            .stress_test::<u32>(Neighbourhood::Eight, Combine::Sum)
+            .collect::<Vec<_>>();
+
+        let square = values::<u64>(Neighbourhood::Eight, Combine::Sum, narrow.len())
+            .iter()
+            .position(|&v| v > u64::from(u32::MAX))
+            .unwrap() as u64
+            + 1;
+
+        assert_eq!(Some(&Err(Overflow { square })), narrow.last());
+        assert!(narrow[..narrow.len() - 1].iter().all(|v| v.is_ok()));
+    }
+}
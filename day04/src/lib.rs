@@ -1,39 +1,183 @@
-pub fn count_valid_passwords(passwords: &[String]) -> usize {
-    passwords.iter().filter(|p| valid_password(p)).count()
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rule {
+    NoDuplicates,
+    NoAnagrams,
+    MinWords(usize),
+    MinWordLength(usize),
+    DenyList(Vec<String>),
 }
 
-pub fn count_passwords_without_anagrams(passwords: &[String]) -> usize {
-    passwords.iter().filter(|p| !contains_anagrams(p)).count()
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Rule::NoDuplicates => write!(f, "no duplicate words"),
+            Rule::NoAnagrams => write!(f, "no anagrams"),
+            Rule::MinWords(count) => write!(f, "at least {} words", count),
+            Rule::MinWordLength(length) => write!(f, "words of at least {} characters", length),
+            Rule::DenyList(ref denied) => write!(f, "none of the words {}", denied.join(", ")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub rule: Rule,
+    pub words: Vec<String>,
+}
+
+// Deny lists are normalized once, when the rule is added or the case sensitivity changes,
+// rather than for every phrase checked. `denied` holds one set per rule, empty for the rules
+// that are not deny lists.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PassphrasePolicy {
+    rules: Vec<Rule>,
+    denied: Vec<HashSet<String>>,
+    case_insensitive: bool,
 }
 
-fn valid_password(password: &str) -> bool {
-    let words = password.split_whitespace().collect::<Vec<_>>();
+// Words are listed once each, in the order they first appear in the phrase.
+fn unique(words: Vec<&str>) -> Vec<String> {
+    let mut seen = HashSet::new();
 
-    let mut unique_words = words.clone();
-    unique_words.sort();
-    unique_words.dedup();
+    words
+        .into_iter()
+        .filter(|w| seen.insert(*w))
+        .map(|w| w.to_owned())
+        .collect()
+}
+
+fn sharing_key<F: Fn(&str) -> String>(words: &[&str], key: F) -> Vec<String> {
+    let keys = words.iter().map(|w| key(w)).collect::<Vec<_>>();
+    let mut counts = HashMap::new();
+
+    for k in &keys {
+        *counts.entry(k.as_str()).or_insert(0) += 1;
+    }
+
+    let shared = words
+        .iter()
+        .zip(&keys)
+        .filter(|&(_, k)| counts[k.as_str()] > 1);
 
-    words.len() == unique_words.len()
+    unique(shared.map(|(&w, _)| w).collect())
 }
 
-fn contains_anagrams(password: &str) -> bool {
-    let words = password.split_whitespace().collect::<Vec<_>>();
+impl PassphrasePolicy {
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        let denied = self.denied_words(&rule);
 
-    for (index, word_1) in words.iter().enumerate() {
-        for word_2 in words[(index + 1)..].iter() {
-            let mut chars_1 = word_1.chars().collect::<Vec<_>>();
-            let mut chars_2 = word_2.chars().collect::<Vec<_>>();
+        self.rules.push(rule);
+        self.denied.push(denied);
+        self
+    }
+
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self.denied = self
+            .rules
+            .iter()
+            .map(|rule| self.denied_words(rule))
+            .collect();
+        self
+    }
 
-            chars_1.sort();
-            chars_2.sort();
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
 
-            if chars_1 == chars_2 {
-                return true;
+    fn normalize(&self, word: &str) -> String {
+        if self.case_insensitive {
+            word.to_lowercase()
+        } else {
+            word.to_owned()
+        }
+    }
+
+    fn denied_words(&self, rule: &Rule) -> HashSet<String> {
+        match *rule {
+            Rule::DenyList(ref denied) => denied.iter().map(|d| self.normalize(d)).collect(),
+            _ => HashSet::new(),
+        }
+    }
+
+    fn offending_words(&self, index: usize, words: &[&str]) -> Option<Vec<String>> {
+        let rule = &self.rules[index];
+
+        let offending = match *rule {
+            Rule::NoDuplicates => sharing_key(words, |w| self.normalize(w)),
+            Rule::NoAnagrams => sharing_key(words, |w| {
+                let mut chars = self.normalize(w).chars().collect::<Vec<_>>();
+                chars.sort_unstable();
+                chars.into_iter().collect()
+            }),
+            Rule::MinWords(count) => {
+                return if words.len() < count {
+                    Some(Vec::new())
+                } else {
+                    None
+                };
             }
+            Rule::MinWordLength(length) => unique(
+                words
+                    .iter()
+                    .cloned()
+                    .filter(|w| w.chars().count() < length)
+                    .collect(),
+            ),
+            Rule::DenyList(_) => {
+                let denied = &self.denied[index];
+                unique(
+                    words
+                        .iter()
+                        .cloned()
+                        .filter(|w| denied.contains(&self.normalize(w)))
+                        .collect(),
+                )
+            }
+        };
+
+        if offending.is_empty() {
+            None
+        } else {
+            Some(offending)
         }
     }
 
-    false
+    pub fn check(&self, phrase: &str) -> Vec<Violation> {
+        let words = phrase.split_whitespace().collect::<Vec<_>>();
+
+        (0..self.rules.len())
+            .filter_map(|index| {
+                self.offending_words(index, &words).map(|words| Violation {
+                    rule: self.rules[index].clone(),
+                    words,
+                })
+            })
+            .collect()
+    }
+
+    pub fn is_valid(&self, phrase: &str) -> bool {
+        self.check(phrase).is_empty()
+    }
+
+    pub fn count_valid<S: AsRef<str>>(&self, phrases: &[S]) -> usize {
+        phrases.iter().filter(|p| self.is_valid(p.as_ref())).count()
+    }
+}
+
+pub fn count_valid_passwords(passwords: &[String]) -> usize {
+    PassphrasePolicy::default()
+        .with_rule(Rule::NoDuplicates)
+        .count_valid(passwords)
+}
+
+pub fn count_passwords_without_anagrams(passwords: &[String]) -> usize {
+    PassphrasePolicy::default()
+        .with_rule(Rule::NoAnagrams)
+        .count_valid(passwords)
 }
 
 #[cfg(test)]
@@ -94,4 +238,76 @@ mod tests {
             count_passwords_without_anagrams(&["oiii ioii iioi iiio".to_owned()])
         );
     }
+
+    fn violation(rule: Rule, words: &[&str]) -> Violation {
+        Violation {
+            rule,
+            words: words.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn report_failed_rules() {
+        let policy = PassphrasePolicy::default()
+            .with_rule(Rule::NoDuplicates)
+            .with_rule(Rule::NoAnagrams)
+            .with_rule(Rule::MinWords(6))
+            .with_rule(Rule::MinWordLength(3));
+
+        assert_eq!(
+            vec![
+                violation(Rule::NoDuplicates, &["aa"]),
+                violation(Rule::NoAnagrams, &["aa", "abcde", "ecdab"]),
+                violation(Rule::MinWords(6), &[]),
+                violation(Rule::MinWordLength(3), &["aa"]),
+            ],
+            policy.check("aa abcde xyz aa ecdab")
+        );
+    }
+
+    #[test]
+    fn deny_words() {
+        let denied = Rule::DenyList(vec!["password".to_owned(), "1234".to_owned()]);
+        let policy = PassphrasePolicy::default().with_rule(denied.clone());
+
+        assert!(policy.is_valid("correct horse battery staple"));
+        assert_eq!(
+            vec![violation(denied, &["1234", "password"])],
+            policy.check("1234 my password 1234")
+        );
+    }
+
+    #[test]
+    fn compare_case_insensitively() {
+        let phrases = ["Listen silent", "AA aa", "Password"];
+        let policy = PassphrasePolicy::default()
+            .with_rule(Rule::NoAnagrams)
+            .with_rule(Rule::DenyList(vec!["PASSWORD".to_owned()]));
+
+        assert_eq!(3, policy.count_valid(&phrases));
+        assert_eq!(
+            0,
+            policy.clone().case_insensitive(true).count_valid(&phrases)
+        );
+        assert_eq!(
+            policy.clone(),
+            policy.case_insensitive(true).case_insensitive(false)
+        );
+
+        let policy = PassphrasePolicy::default()
+            .case_insensitive(true)
+            .with_rule(Rule::DenyList(vec!["PASSWORD".to_owned()]));
+
+        assert!(!policy.is_valid("my password"));
+    }
+
+    #[test]
+    fn describe_rules() {
+        assert_eq!("at least 3 words", Rule::MinWords(3).to_string());
+        assert_eq!("no anagrams", Rule::NoAnagrams.to_string());
+        assert_eq!(
+            "none of the words password, 1234",
+            Rule::DenyList(vec!["password".to_owned(), "1234".to_owned()]).to_string()
+        );
+    }
 }
@@ -1,15 +1,12 @@
 extern crate day04;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day04::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-    let passwords = file.lines()
-        .map(|l| l.expect("error reading file"))
-        .collect::<Vec<_>>();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let passwords = input.lines().map(|(_, l)| l.to_owned()).collect::<Vec<_>>();
 
     println!("Valid passwords: {}", count_valid_passwords(&passwords));
 
@@ -1,15 +1,12 @@
 extern crate day05;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day05::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-    let offsets = file.lines()
-        .map(|i| i.expect("error reading file").parse().unwrap_or(0))
-        .collect::<Vec<_>>();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let offsets = input.values::<i32>().unwrap_or_else(|e| e.exit());
 
     println!("Number of steps: {}", number_of_steps(&mut offsets.clone()));
 
@@ -5,7 +5,7 @@ pub fn max_steps_and_cycle_length(blocks: &mut [u32]) -> (u32, u32) {
     let mut steps = 0;
     let len = blocks.len();
 
-    while !seen.contains_key(&blocks.to_vec()) {
+    while !seen.contains_key(blocks) {
         seen.insert(blocks.to_vec(), steps);
         steps += 1;
 
@@ -23,7 +23,7 @@ pub fn max_steps_and_cycle_length(blocks: &mut [u32]) -> (u32, u32) {
         }
     }
 
-    (steps, steps - seen.get(&blocks.to_vec()).unwrap())
+    (steps, steps - seen.get(blocks).unwrap())
 }
 
 #[cfg(test)]
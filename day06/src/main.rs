@@ -1,22 +1,12 @@
 extern crate day06;
-
-use std::fs::File;
-use std::io::prelude::*;
+extern crate input;
 
 use day06::*;
+use input::Input;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
-
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
-
-    let mut blocks = contents
-        .split_whitespace()
-        .map(|i| i.parse().unwrap_or(0))
-        .collect::<Vec<_>>();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let mut blocks = input.tokens().unwrap_or_else(|e| e.exit());
 
     let max_steps_and_cycle_length = max_steps_and_cycle_length(&mut blocks);
 
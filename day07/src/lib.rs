@@ -1,9 +1,10 @@
+extern crate input;
+
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::fmt;
 
-#[derive(Debug, PartialEq)]
-pub struct ProgramParseError;
+use input::{ParseError, Tokens};
 
 #[derive(Debug, PartialEq)]
 pub struct Program {
@@ -62,29 +63,34 @@ impl Program {
 }
 
 impl FromStr for Program {
-    type Err = ProgramParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
+        let mut tokens = Tokens::new(s);
 
-        let name = match parts.next() {
-            Some(n) => n.to_owned(),
-            None => return Err(ProgramParseError),
-        };
+        let name = tokens.expect("a program name")?.text.to_owned();
 
-        let weight = match parts.next() {
-            Some(w) => {
-                w.trim_matches(|p| p == '(' || p == ')').parse().unwrap_or(
-                    0,
-                )
-            }
-            None => return Err(ProgramParseError),
-        };
+        let weight = tokens.expect("a weight")?;
+        let weight = weight
+            .text
+            .trim_matches(|p| p == '(' || p == ')')
+            .parse()
+            .map_err(|_| weight.error("a weight in parentheses"))?;
 
         let mut children = Vec::new();
 
-        if let Some(_) = parts.next() {
-            children = parts.map(|p| p.trim_matches(',').to_owned()).collect();
+        if let Some(arrow) = tokens.next() {
+            if arrow.text != "->" {
+                return Err(arrow.error("`->`"));
+            }
+
+            children = tokens
+                .map(|t| t.text.trim_matches(',').to_owned())
+                .collect();
+
+            if children.is_empty() {
+                return Err(ParseError::at_end(s, "a child program name"));
+            }
         }
 
         Ok(Program {
@@ -105,6 +111,12 @@ pub struct Tower {
     programs: HashMap<String, Program>,
 }
 
+impl Default for Tower {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Tower {
     pub fn new() -> Self {
         Tower { programs: HashMap::new() }
@@ -120,9 +132,10 @@ impl Tower {
 
     pub fn head(&self) -> Option<&Program> {
         self.programs.values().find(|p| {
-            self.programs.values().find(
-                |i| i.children.contains(&p.name),
-            ) == None
+            self.programs
+                .values()
+                .find(|i| i.children.contains(&p.name))
+                .is_none()
         })
     }
 
@@ -141,12 +154,38 @@ mod tests {
 
     #[test]
     fn parse_empty_string() {
-        assert_eq!(Err(ProgramParseError), "".parse::<Program>());
+        let error = "".parse::<Program>().unwrap_err();
+
+        assert_eq!(
+            "expected a program name, found end of input",
+            error.message()
+        );
     }
 
     #[test]
     fn parse_name_only() {
-        assert_eq!(Err(ProgramParseError), "abcd".parse::<Program>());
+        let error = "abcd".parse::<Program>().unwrap_err();
+
+        assert_eq!(4..4, error.span());
+        assert_eq!("a weight", error.expected());
+    }
+
+    #[test]
+    fn parse_invalid_weight() {
+        let error = "abcd (1x)".parse::<Program>().unwrap_err();
+
+        assert_eq!(5..9, error.span());
+        assert_eq!(
+            "expected a weight in parentheses, found `(1x)`",
+            error.message()
+        );
+    }
+
+    #[test]
+    fn parse_missing_children() {
+        let error = "abcd (10) ->".parse::<Program>().unwrap_err();
+
+        assert_eq!("a child program name", error.expected());
     }
 
     #[test]
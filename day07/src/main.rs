@@ -1,18 +1,15 @@
 extern crate day07;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day07::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
     let mut tower = Tower::new();
 
-    for l in file.lines() {
-        if let Ok(program) = l.expect("error reading file").parse() {
-            tower.add(program);
-        }
+    for program in input.records().unwrap_or_else(|e| e.exit()) {
+        tower.add(program);
     }
 
     if let Some(program) = tower.head() {
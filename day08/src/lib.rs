@@ -1,6 +1,10 @@
+extern crate input;
+
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use input::{ParseError, Tokens};
+
 #[derive(Debug, PartialEq)]
 pub enum Operation {
     Inc(i32),
@@ -17,9 +21,6 @@ pub enum Condition {
     Ne(i32),
 }
 
-#[derive(Debug, PartialEq)]
-pub struct InstructionParseError;
-
 #[derive(Debug, PartialEq)]
 pub struct Instruction {
     register: String,
@@ -44,46 +45,48 @@ impl Instruction {
     }
 }
 
-fn next_part<T: FromStr>(parts: &mut Iterator<Item = &str>) -> Result<T, InstructionParseError> {
-    if let Some(s) = parts.next() {
-        s.parse().map_err(|_| InstructionParseError)
-    } else {
-        Err(InstructionParseError)
-    }
+fn next_part<T: FromStr>(tokens: &mut Tokens, expected: &str) -> Result<T, ParseError> {
+    tokens.expect(expected)?.parse(expected)
 }
 
 impl FromStr for Instruction {
-    type Err = InstructionParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
+        let mut tokens = Tokens::new(s);
 
-        let register = next_part(&mut parts)?;
-        let oper = next_part::<String>(&mut parts)?;
-        let argument = next_part(&mut parts)?;
+        let register = next_part(&mut tokens, "a register name")?;
+        let oper = tokens.expect("`inc` or `dec`")?;
+        let argument = next_part(&mut tokens, "a number")?;
 
-        let operation = match oper.as_str() {
+        let operation = match oper.text {
             "inc" => Operation::Inc(argument),
             "dec" => Operation::Dec(argument),
-            _ => return Err(InstructionParseError),
+            _ => return Err(oper.error("`inc` or `dec`")),
         };
 
-        let _if_keyword = next_part::<String>(&mut parts)?;
+        let if_keyword = tokens.expect("`if`")?;
+
+        if if_keyword.text != "if" {
+            return Err(if_keyword.error("`if`"));
+        }
 
-        let conditional_register = next_part(&mut parts)?;
-        let condition_oper = next_part::<String>(&mut parts)?;
-        let condition_argument = next_part(&mut parts)?;
+        let conditional_register = next_part(&mut tokens, "a register name")?;
+        let condition_oper = tokens.expect("a comparison operator")?;
+        let condition_argument = next_part(&mut tokens, "a number")?;
 
-        let condition = match condition_oper.as_str() {
+        let condition = match condition_oper.text {
             "<" => Condition::Lt(condition_argument),
             ">" => Condition::Gt(condition_argument),
             ">=" => Condition::Ge(condition_argument),
             "<=" => Condition::Le(condition_argument),
             "==" => Condition::Eq(condition_argument),
             "!=" => Condition::Ne(condition_argument),
-            _ => return Err(InstructionParseError),
+            _ => return Err(condition_oper.error("a comparison operator")),
         };
 
+        tokens.end()?;
+
         Ok(Instruction {
             register,
             operation,
@@ -98,6 +101,12 @@ pub struct Processor {
     largest_value_overall: i32,
 }
 
+impl Default for Processor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Processor {
     pub fn new() -> Self {
         Processor {
@@ -141,10 +150,7 @@ impl Processor {
     }
 
     pub fn value(&self, register: &str) -> i32 {
-        self.registers
-            .get(&register.to_string())
-            .unwrap_or(&0)
-            .clone()
+        *self.registers.get(register).unwrap_or(&0)
     }
 
     pub fn largest_value(&self) -> i32 {
@@ -188,6 +194,29 @@ mod tests {
         assert_eq!(Ok(instruction), "c inc -20 if c == 10".parse());
     }
 
+    #[test]
+    fn parse_invalid_operation() {
+        let error = "b mul 5 if a > 1".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(2..5, error.span());
+        assert_eq!("expected `inc` or `dec`, found `mul`", error.message());
+    }
+
+    #[test]
+    fn parse_invalid_condition() {
+        let error = "b inc 5 if a => 1".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(13..15, error.span());
+        assert_eq!("a comparison operator", error.expected());
+    }
+
+    #[test]
+    fn parse_truncated_instruction() {
+        let error = "b inc 5 if a >".parse::<Instruction>().unwrap_err();
+
+        assert_eq!("expected a number, found end of input", error.message());
+    }
+
     #[test]
     fn execute_instructions() {
         let mut processor = Processor::new();
@@ -1,18 +1,15 @@
 extern crate day08;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day08::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
     let mut processor = Processor::new();
 
-    for l in file.lines() {
-        if let Ok(instruction) = l.expect("error reading file").parse() {
-            processor.execute(&instruction);
-        }
+    for instruction in input.records().unwrap_or_else(|e| e.exit()) {
+        processor.execute(&instruction);
     }
 
     println!("Largest register value: {}", processor.largest_value());
@@ -23,7 +23,7 @@ pub fn parse_stream(stream: &str) -> StreamData {
     parse_group(&mut stream.chars(), 0)
 }
 
-fn parse_group(stream: &mut Iterator<Item = char>, base_score: u32) -> StreamData {
+fn parse_group(stream: &mut dyn Iterator<Item = char>, base_score: u32) -> StreamData {
     let mut stream_data = StreamData::new(base_score, 0);
 
     while let Some(c) = stream.next() {
@@ -41,7 +41,7 @@ fn parse_group(stream: &mut Iterator<Item = char>, base_score: u32) -> StreamDat
     stream_data
 }
 
-fn parse_garbage(stream: &mut Iterator<Item = char>) -> StreamData {
+fn parse_garbage(stream: &mut dyn Iterator<Item = char>) -> StreamData {
     let mut stream_data = StreamData::new(0, 0);
 
     while let Some(c) = stream.next() {
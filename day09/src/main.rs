@@ -1,17 +1,13 @@
 extern crate day09;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day09::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-    let stream = file.lines().next().expect("no stream given").expect(
-        "error reading file",
-    );
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
 
-    let stream_data = parse_stream(&stream);
+    let stream_data = parse_stream(input.trimmed());
 
     println!("Total score: {}", stream_data.score);
     println!("Garbage amount: {}", stream_data.garbage);
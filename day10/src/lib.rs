@@ -1,38 +1,32 @@
-const LIST_SIZE: usize = 256;
-const ROUNDS: usize = 64;
-const BYTES_SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
-const CHUNKS: usize = 16;
+extern crate input;
+extern crate knot_hash;
 
-pub fn hash(bytes: &[u8]) -> String {
-    let mut list = (0..LIST_SIZE).map(|i| i as u8).collect::<Vec<_>>();
-
-    let mut position = 0;
-    let mut skip_size = 0;
-
-    for _ in 0..ROUNDS {
-        for &length in bytes.iter().chain(BYTES_SUFFIX.iter()) {
-            for i in 0..(length as usize / 2) {
-                list.swap(
-                    (position + i) % LIST_SIZE,
-                    (position + (length as usize - 1 - i)) % LIST_SIZE,
-                );
-            }
-
-            position += length as usize + skip_size;
-            skip_size += 1;
-        }
-    }
+use input::ParseError;
+
+pub fn check_product(lengths: &str) -> Result<u32, ParseError> {
+    knot_hash::single_round(knot_hash::LIST_SIZE, lengths).map(|r| r.product)
+}
 
-    list.chunks(CHUNKS)
-        .map(|c| c.iter().fold(0, |acc, &x| acc ^ x))
-        .map(|b| format!("{:02x}", b))
-        .collect()
+pub fn hash(bytes: &[u8]) -> String {
+    knot_hash::to_hex(&knot_hash::hash(bytes))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn check_product_of_first_two() {
+        assert_eq!(2, check_product("3").unwrap());
+    }
+
+    #[test]
+    fn reject_invalid_length() {
+        let error = check_product("3,4,300").unwrap_err();
+
+        assert_eq!("300", error.found());
+    }
+
     #[test]
     fn hash_empty_string() {
         assert_eq!("a2582a3a0e66e6e86e3812dcb672a272", hash("".as_bytes()));
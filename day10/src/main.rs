@@ -1,17 +1,14 @@
 extern crate day10;
-
-use std::fs::File;
-use std::io::prelude::*;
+extern crate input;
 
 use day10::*;
+use input::Input;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
 
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
+    let product = check_product(input.trimmed()).unwrap_or_else(|e| input.syntax(1, e).exit());
 
-    println!("Hash: {}", hash(contents.trim().as_bytes()));
+    println!("Check product: {}", product);
+    println!("Hash: {}", hash(input.trimmed().as_bytes()));
 }
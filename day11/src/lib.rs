@@ -1,5 +1,9 @@
+extern crate input;
+
 use std::str::FromStr;
 
+use input::ParseError;
+
 #[derive(Debug, PartialEq)]
 pub enum Direction {
     N,
@@ -10,13 +14,13 @@ pub enum Direction {
     NW,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct ParseDirectionError;
-
 impl FromStr for Direction {
-    type Err = ParseDirectionError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let start = s.len() - s.trim_start().len();
+        let end = s.trim_end().len().max(start);
+
         match s.trim() {
             "n" => Ok(Direction::N),
             "ne" => Ok(Direction::NE),
@@ -24,7 +28,7 @@ impl FromStr for Direction {
             "s" => Ok(Direction::S),
             "sw" => Ok(Direction::SW),
             "nw" => Ok(Direction::NW),
-            _ => Err(ParseDirectionError),
+            _ => Err(ParseError::new(s, start..end, "a direction")),
         }
     }
 }
@@ -37,6 +41,12 @@ pub struct HexCoords {
     max_distance: u32,
 }
 
+impl Default for HexCoords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HexCoords {
     pub fn new() -> Self {
         HexCoords {
@@ -85,7 +95,7 @@ impl HexCoords {
     pub fn distance_from_origin(&self) -> u32 {
         [self.x, self.y, self.z]
             .iter()
-            .map(|i| i.abs() as u32)
+            .map(|i| i.unsigned_abs())
             .max()
             .unwrap()
     }
@@ -99,6 +109,20 @@ impl HexCoords {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_directions() {
+        assert_eq!(Ok(Direction::NE), "ne".parse());
+        assert_eq!(Ok(Direction::S), " s\n".parse());
+    }
+
+    #[test]
+    fn parse_invalid_direction() {
+        let error = " nn ".parse::<Direction>().unwrap_err();
+
+        assert_eq!(1..3, error.span());
+        assert_eq!("expected a direction, found `nn`", error.message());
+    }
+
     #[test]
     fn going_northeast() {
         let mut coords = HexCoords::new();
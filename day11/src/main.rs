@@ -1,25 +1,14 @@
 extern crate day11;
-
-use std::fs::File;
-use std::io::prelude::*;
+extern crate input;
 
 use day11::*;
+use input::Input;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
-
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
-
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
     let mut coords = HexCoords::new();
 
-    for direction in contents
-        .split(',')
-        .map(|i| i.parse().expect("error parsing direction"))
-        .collect::<Vec<_>>()
-    {
+    for direction in input.fields(',').unwrap_or_else(|e| e.exit()) {
         coords.take_step(direction);
     }
 
@@ -8,6 +8,12 @@ struct Node {
     neighbours: HashSet<u32>,
 }
 
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Graph {
     pub fn new() -> Self {
         Graph { nodes: HashMap::new() }
@@ -25,7 +31,7 @@ impl Graph {
 
         let mut neighbours = Vec::new();
 
-        while let Some(i) = iter.next() {
+        for i in iter {
             let n = i.trim_matches(',').parse().expect("invalid neighbour data");
             neighbours.push(n);
         }
@@ -44,7 +50,7 @@ impl Graph {
             let node = self.nodes.entry(a).or_insert(Node::new());
 
             if a != b {
-                (*node).neighbours.insert(b);
+                node.neighbours.insert(b);
             }
         }
 
@@ -52,7 +58,7 @@ impl Graph {
             let node = self.nodes.entry(b).or_insert(Node::new());
 
             if a != b {
-                (*node).neighbours.insert(a);
+                node.neighbours.insert(a);
             }
         }
     }
@@ -1,17 +1,15 @@
 extern crate day12;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day12::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
     let mut graph = Graph::new();
 
-    for l in file.lines() {
-        let l = l.expect("error reading file");
-        graph.parse_node(&l);
+    for (_, l) in input.lines() {
+        graph.parse_node(l);
     }
 
     println!("Nodes in group 0: {}", graph.nodes_in_group(0).len());
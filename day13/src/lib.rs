@@ -1,7 +1,17 @@
+extern crate input;
+
+use input::{Input, ParseError, Tokens};
+
 pub struct Firewall {
     layers: Vec<Option<Layer>>,
 }
 
+impl Default for Firewall {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Firewall {
     pub fn new() -> Self {
         Firewall { layers: Vec::new() }
@@ -15,13 +25,35 @@ impl Firewall {
         self.layers.push(Some(Layer::new(range)));
     }
 
-    pub fn parse_layer(&mut self, s: &str) {
-        let mut iter = s.split_whitespace();
+    pub fn parse_layer(&mut self, s: &str) -> Result<(), ParseError> {
+        let mut tokens = Tokens::new(s);
 
-        let depth = iter.next().unwrap().trim_matches(':').parse().unwrap_or(0);
-        let range = iter.next().unwrap().parse().unwrap_or(0);
+        let depth = tokens.expect("a depth")?;
+        let depth = depth
+            .text
+            .trim_end_matches(':')
+            .parse()
+            .map_err(|_| depth.error("a depth followed by `:`"))?;
+
+        let range = tokens.expect("a range")?.parse("a range")?;
+
+        tokens.end()?;
 
         self.add_layer(depth, range);
+
+        Ok(())
+    }
+
+    pub fn parse(input: &Input) -> Result<Self, input::Error> {
+        let mut firewall = Firewall::new();
+
+        for (number, l) in input.lines().filter(|&(_, l)| !l.trim().is_empty()) {
+            firewall
+                .parse_layer(l)
+                .map_err(|e| input.syntax(number, e))?;
+        }
+
+        Ok(firewall)
     }
 
     pub fn trip_severity(&self) -> u32 {
@@ -75,7 +107,7 @@ impl Layer {
     }
 
     fn scanner_hit(&self, t: u32) -> bool {
-        t % ((self.range - 1) * 2) == 0
+        t.is_multiple_of((self.range - 1) * 2)
     }
 }
 
@@ -95,6 +127,45 @@ mod tests {
         assert_eq!(24, firewall.trip_severity());
     }
 
+    #[test]
+    fn parse_layers() {
+        let mut firewall = Firewall::new();
+
+        assert_eq!(Ok(()), firewall.parse_layer("0: 3"));
+        assert_eq!(Ok(()), firewall.parse_layer("1: 2"));
+        assert_eq!(Ok(()), firewall.parse_layer("4: 4"));
+        assert_eq!(Ok(()), firewall.parse_layer("6: 4"));
+
+        assert_eq!(24, firewall.trip_severity());
+    }
+
+    #[test]
+    fn parse_input_with_blank_lines() {
+        let input = Input::embedded("input.txt", "0: 3\n1: 2\n\n4: 4\n6: 4\n\n");
+        let firewall = Firewall::parse(&input).unwrap();
+
+        assert_eq!(24, firewall.trip_severity());
+
+        let input = Input::embedded("input.txt", "0: 3\n\n1: x\n");
+        let error = Firewall::parse(&input).err().unwrap();
+
+        assert!(error.to_string().contains("input.txt:3:4"));
+    }
+
+    #[test]
+    fn parse_invalid_layer() {
+        let mut firewall = Firewall::new();
+
+        let error = firewall.parse_layer("0: x").unwrap_err();
+        assert_eq!("expected a range, found `x`", error.message());
+
+        let error = firewall.parse_layer("0:").unwrap_err();
+        assert_eq!("expected a range, found end of input", error.message());
+
+        let error = firewall.parse_layer("a: 3").unwrap_err();
+        assert_eq!(0..2, error.span());
+    }
+
     #[test]
     fn calculate_delay_for_safe_trip() {
         let mut firewall = Firewall::new();
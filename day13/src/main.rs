@@ -1,18 +1,12 @@
 extern crate day13;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day13::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-    let mut firewall = Firewall::new();
-
-    for l in file.lines() {
-        let l = l.expect("error reading file");
-        firewall.parse_layer(&l);
-    }
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let firewall = Firewall::parse(&input).unwrap_or_else(|e| e.exit());
 
     println!("Severity of trip: {}", firewall.trip_severity());
     println!("Delay needed for safe trip: {}", firewall.safe_trip_delay());
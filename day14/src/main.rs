@@ -1,19 +1,13 @@
 extern crate day14;
-
-use std::fs::File;
-use std::io::prelude::*;
+extern crate input;
 
 use day14::*;
+use input::Input;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
-
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
 
-    let grid = Grid::new(&contents.trim());
+    let grid = Grid::new(input.trimmed());
 
     println!("Used squares: {}", grid.used_squares());
     println!("Regions: {}", grid.regions());
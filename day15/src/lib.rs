@@ -28,7 +28,7 @@ impl Iterator for Generator {
         loop {
             self.previous = (self.previous * self.factor) % DIVISOR;
 
-            if self.previous % self.multiples_of == 0 {
+            if self.previous.is_multiple_of(self.multiples_of) {
                 return Some(self.previous);
             }
         }
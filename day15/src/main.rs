@@ -1,35 +1,32 @@
 extern crate day15;
+extern crate input;
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::process;
 
 use day15::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-    let mut lines = file.lines();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
 
-    let generator_a_previous = lines
-        .next()
-        .expect("error reading file")
-        .expect("error reading file")
-        .split_whitespace()
-        .last()
-        .expect("starting value not present")
-        .parse()
-        .expect("cannot parse starting value");
+    let starting_values = input
+        .lines()
+        .map(|(number, l)| {
+            input
+                .parse(number, l.split_whitespace().last().unwrap_or(""))
+                .unwrap_or_else(|e| e.exit())
+        })
+        .collect::<Vec<_>>();
 
-    let generator_b_previous = lines
-        .next()
-        .expect("error reading file")
-        .expect("error reading file")
-        .split_whitespace()
-        .last()
-        .expect("starting value not present")
-        .parse()
-        .expect("cannot parse starting value");
+    if starting_values.len() != 2 {
+        eprintln!(
+            "{}: expected starting values for two generators",
+            input.name()
+        );
+        process::exit(1);
+    }
 
-    let judge = Judge::new(generator_a_previous, generator_b_previous);
+    let judge = Judge::new(starting_values[0], starting_values[1]);
 
     println!("Matches found: {}", judge.count_matches(5_000_000));
 }
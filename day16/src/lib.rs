@@ -1,55 +1,60 @@
+extern crate input;
+
+use std::ops::Range;
 use std::str::FromStr;
 
+use input::ParseError;
+
+#[derive(Debug)]
 pub enum DanceMove {
     Spin(usize),
     Exchange(usize, usize),
     Partner(char, char),
 }
 
-#[derive(Debug)]
-pub struct DanceMoveParseError;
+fn argument<T: FromStr>(s: &str, span: Range<usize>, expected: &str) -> Result<T, ParseError> {
+    s[span.clone()]
+        .parse()
+        .map_err(|_| ParseError::new(s, span, expected))
+}
+
+fn arguments<T: FromStr>(
+    s: &str,
+    span: Range<usize>,
+    expected: &str,
+) -> Result<(T, T), ParseError> {
+    match s[span.clone()].find('/') {
+        Some(i) => Ok((
+            argument(s, span.start..(span.start + i), expected)?,
+            argument(s, (span.start + i + 1)..span.end, expected)?,
+        )),
+        None => Err(ParseError::new(s, span.end..span.end, "`/`")),
+    }
+}
 
 impl FromStr for DanceMove {
-    type Err = DanceMoveParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut chars = s.trim().chars();
-
-        match chars.next() {
-            Some('s') => {
-                chars
-                    .collect::<String>()
-                    .parse()
-                    .map(|s| DanceMove::Spin(s))
-                    .map_err(|_| DanceMoveParseError)
-            }
-            Some('x') => {
-                let s = chars.collect::<String>();
-                let mut parts = s.split('/');
+        let start = s.len() - s.trim_start().len();
+        let end = s.trim_end().len().max(start);
 
-                let a = parts.next().unwrap().parse().map_err(
-                    |_| DanceMoveParseError,
-                )?;
-                let b = parts.next().unwrap().parse().map_err(
-                    |_| DanceMoveParseError,
-                )?;
+        let kind = s[start..].chars().next();
+        let rest = (start + kind.map_or(0, |c| c.len_utf8()))..end;
+
+        match kind {
+            Some('s') => argument(s, rest, "a spin size").map(DanceMove::Spin),
+            Some('x') => {
+                let (a, b) = arguments(s, rest, "a position")?;
 
                 Ok(DanceMove::Exchange(a, b))
             }
             Some('p') => {
-                let s = chars.collect::<String>();
-                let mut parts = s.split('/');
-
-                let a = parts.next().unwrap().parse().map_err(
-                    |_| DanceMoveParseError,
-                )?;
-                let b = parts.next().unwrap().parse().map_err(
-                    |_| DanceMoveParseError,
-                )?;
+                let (a, b) = arguments(s, rest, "a program name")?;
 
                 Ok(DanceMove::Partner(a, b))
             }
-            _ => Err(DanceMoveParseError),
+            _ => Err(ParseError::new(s, start..rest.start, "a dance move")),
         }
     }
 }
@@ -60,9 +65,7 @@ pub struct Dance {
 
 impl Dance {
     pub fn new(count: u8) -> Self {
-        let programs = (('a' as u8)..('a' as u8 + count))
-            .map(|i| i as char)
-            .collect();
+        let programs = (b'a'..(b'a' + count)).map(|i| i as char).collect();
 
         Dance { programs }
     }
@@ -106,7 +109,7 @@ impl Dance {
 
             let order = self.order();
 
-            if history.get(0).unwrap_or(&String::new()) == &order {
+            if history.first().unwrap_or(&String::new()) == &order {
                 return history[(rounds - 1) % i].clone();
             } else {
                 history.push(order);
@@ -121,6 +124,35 @@ impl Dance {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_dance_moves() {
+        match "x3/4".parse() {
+            Ok(DanceMove::Exchange(3, 4)) => (),
+            _ => panic!("expected exchange"),
+        }
+
+        match " pe/b\n".parse() {
+            Ok(DanceMove::Partner('e', 'b')) => (),
+            _ => panic!("expected partner"),
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_dance_moves() {
+        let error = "q1".parse::<DanceMove>().unwrap_err();
+        assert_eq!("expected a dance move, found `q`", error.message());
+
+        let error = "x3/y".parse::<DanceMove>().unwrap_err();
+        assert_eq!(3..4, error.span());
+        assert_eq!("a position", error.expected());
+
+        let error = "pab".parse::<DanceMove>().unwrap_err();
+        assert_eq!("expected `/`, found end of input", error.message());
+
+        let error = "s".parse::<DanceMove>().unwrap_err();
+        assert_eq!("expected a spin size, found end of input", error.message());
+    }
+
     #[test]
     fn test_dance_moves() {
         let mut dance = Dance::new(5);
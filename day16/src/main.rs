@@ -1,24 +1,14 @@
 extern crate day16;
-
-use std::fs::File;
-use std::io::prelude::*;
+extern crate input;
 
 use day16::*;
+use input::Input;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
-
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
-
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
     let mut dance = Dance::new(16);
 
-    let dance_moves = contents
-        .split(',')
-        .map(|s| s.parse().expect("unable to parse dance move"))
-        .collect::<Vec<_>>();
+    let dance_moves = input.fields(',').unwrap_or_else(|e| e.exit());
 
     println!(
         "Final order: {}",
@@ -1,19 +1,12 @@
 extern crate day17;
-
-use std::fs::File;
-use std::io::prelude::*;
+extern crate input;
 
 use day17::*;
+use input::Input;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("file not found");
-    let mut contents = String::new();
-
-    file.read_to_string(&mut contents).expect(
-        "error reading file",
-    );
-
-    let mut spinlock = Spinlock::new(contents.trim().parse().expect("invalid value"));
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let mut spinlock = Spinlock::new(input.value().unwrap_or_else(|e| e.exit()));
 
     println!("Value after latest: {}", spinlock.value_after_latest(2017));
 
@@ -1,9 +1,13 @@
+extern crate input;
+
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::thread;
 use std::sync::mpsc;
 use std::time::Duration;
 
+use input::{ParseError, Tokens};
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Register(char),
@@ -19,88 +23,100 @@ pub enum Instruction {
     Mod(char, Value),
     Rcv(char),
     Jgz(Value, Value),
+    Sub(char, Value),
+    Jnz(Value, Value),
 }
 
-#[derive(Debug, Clone)]
-pub struct InstructionParseError;
+const NAMES: [&str; 9] = [
+    "snd", "set", "add", "mul", "mod", "rcv", "jgz", "sub", "jnz",
+];
+
+impl Instruction {
+    fn opcode(&self) -> usize {
+        match *self {
+            Instruction::Snd(_) => 0,
+            Instruction::Set(..) => 1,
+            Instruction::Add(..) => 2,
+            Instruction::Mul(..) => 3,
+            Instruction::Mod(..) => 4,
+            Instruction::Rcv(_) => 5,
+            Instruction::Jgz(..) => 6,
+            Instruction::Sub(..) => 7,
+            Instruction::Jnz(..) => 8,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        NAMES[self.opcode()]
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    Executed,
+    Sent,
+    Blocked,
+    Halted,
+}
+
+fn register(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => Some(c),
+        _ => None,
+    }
+}
 
 impl FromStr for Value {
-    type Err = InstructionParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(n) = s.parse::<i64>() {
             Ok(Value::Number(n))
         } else {
-            s.chars().next().map(|n| Value::Register(n)).ok_or(
-                InstructionParseError,
-            )
+            register(s)
+                .map(Value::Register)
+                .ok_or_else(|| ParseError::new(s, 0..s.len(), "a register or a number"))
         }
     }
 }
 
-impl FromStr for Instruction {
-    type Err = InstructionParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
+fn next_register(tokens: &mut Tokens) -> Result<char, ParseError> {
+    let token = tokens.expect("a register")?;
 
-        match parts.next() {
-            Some("snd") => {
-                let v = parts.next().ok_or(InstructionParseError)?;
+    register(token.text).ok_or_else(|| token.error("a register"))
+}
 
-                Ok(Instruction::Snd(v.parse()?))
-            }
-            Some("set") => {
-                let n = parts.next().ok_or(InstructionParseError)?;
-                let v = parts.next().ok_or(InstructionParseError)?;
-
-                Ok(Instruction::Set(
-                    n.chars().next().ok_or(InstructionParseError)?,
-                    v.parse()?,
-                ))
-            }
-            Some("add") => {
-                let n = parts.next().ok_or(InstructionParseError)?;
-                let v = parts.next().ok_or(InstructionParseError)?;
-
-                Ok(Instruction::Add(
-                    n.chars().next().ok_or(InstructionParseError)?,
-                    v.parse()?,
-                ))
-            }
-            Some("mul") => {
-                let n = parts.next().ok_or(InstructionParseError)?;
-                let v = parts.next().ok_or(InstructionParseError)?;
-
-                Ok(Instruction::Mul(
-                    n.chars().next().ok_or(InstructionParseError)?,
-                    v.parse()?,
-                ))
-            }
-            Some("mod") => {
-                let n = parts.next().ok_or(InstructionParseError)?;
-                let v = parts.next().ok_or(InstructionParseError)?;
-
-                Ok(Instruction::Mod(
-                    n.chars().next().ok_or(InstructionParseError)?,
-                    v.parse()?,
-                ))
-            }
-            Some("rcv") => {
-                let n = parts.next().ok_or(InstructionParseError)?;
+fn next_value(tokens: &mut Tokens) -> Result<Value, ParseError> {
+    tokens
+        .expect("a register or a number")?
+        .parse("a register or a number")
+}
 
-                Ok(Instruction::Rcv(
-                    n.chars().next().ok_or(InstructionParseError)?,
-                ))
-            }
-            Some("jgz") => {
-                let v = parts.next().ok_or(InstructionParseError)?;
-                let o = parts.next().ok_or(InstructionParseError)?;
+impl FromStr for Instruction {
+    type Err = ParseError;
 
-                Ok(Instruction::Jgz(v.parse()?, o.parse()?))
-            }
-            _ => Err(InstructionParseError),
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokens::new(s);
+        let name = tokens.expect("an instruction")?;
+
+        let instruction = match name.text {
+            "snd" => Instruction::Snd(next_value(&mut tokens)?),
+            "set" => Instruction::Set(next_register(&mut tokens)?, next_value(&mut tokens)?),
+            "add" => Instruction::Add(next_register(&mut tokens)?, next_value(&mut tokens)?),
+            "mul" => Instruction::Mul(next_register(&mut tokens)?, next_value(&mut tokens)?),
+            "mod" => Instruction::Mod(next_register(&mut tokens)?, next_value(&mut tokens)?),
+            "rcv" => Instruction::Rcv(next_register(&mut tokens)?),
+            "jgz" => Instruction::Jgz(next_value(&mut tokens)?, next_value(&mut tokens)?),
+            "sub" => Instruction::Sub(next_register(&mut tokens)?, next_value(&mut tokens)?),
+            "jnz" => Instruction::Jnz(next_value(&mut tokens)?, next_value(&mut tokens)?),
+            _ => return Err(name.error("an instruction")),
+        };
+
+        tokens.end()?;
+
+        Ok(instruction)
     }
 }
 
@@ -111,6 +127,7 @@ pub struct Program {
     pc: i64,
     receiver: mpsc::Receiver<i64>,
     senders: HashMap<usize, mpsc::Sender<i64>>,
+    executed: [u64; 9],
 }
 
 impl Program {
@@ -130,65 +147,113 @@ impl Program {
             pc: 0,
             receiver,
             senders,
+            executed: [0; 9],
         }
     }
 
-    pub fn execute(&mut self) -> u32 {
-        let mut values_sent = 0;
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
 
-        loop {
-            let mut pc_updated = false;
+    pub fn register(&self, name: char) -> i64 {
+        self.registers.get(&name).cloned().unwrap_or(0)
+    }
 
-            match self.instructions[self.pc as usize] {
-                Instruction::Snd(ref value) => {
-                    let v = self.get_value(value);
+    pub fn set_register(&mut self, name: char, value: i64) {
+        self.registers.insert(name, value);
+    }
 
-                    for (_, sender) in self.senders.iter().filter(|&(pid, _)| pid != &self.pid) {
-                        sender.send(v).unwrap();
-                    }
+    pub fn executed(&self, name: &str) -> u64 {
+        NAMES
+            .iter()
+            .position(|&n| n == name)
+            .map_or(0, |opcode| self.executed[opcode])
+    }
 
-                    values_sent += 1;
+    pub fn step(&mut self) -> Step {
+        if self.pc < 0 || self.pc >= self.instructions.len() as i64 {
+            return Step::Halted;
+        }
+
+        let instruction = &self.instructions[self.pc as usize];
+        let mut step = Step::Executed;
+        let mut pc_updated = false;
+
+        self.executed[instruction.opcode()] += 1;
+
+        match *instruction {
+            Instruction::Snd(ref value) => {
+                let v = self.get_value(value);
+
+                for (_, sender) in self.senders.iter().filter(|&(pid, _)| pid != &self.pid) {
+                    sender.send(v).unwrap();
                 }
-                Instruction::Set(name, ref value) => {
-                    let v = self.get_value(value);
+
+                step = Step::Sent;
+            }
+            Instruction::Set(name, ref value) => {
+                let v = self.get_value(value);
+                *self.registers.entry(name).or_insert(0) = v;
+            }
+            Instruction::Add(name, ref value) => {
+                let v = self.get_value(value);
+                *self.registers.entry(name).or_insert(0) += v;
+            }
+            Instruction::Sub(name, ref value) => {
+                let v = self.get_value(value);
+                *self.registers.entry(name).or_insert(0) -= v;
+            }
+            Instruction::Mul(name, ref value) => {
+                let v = self.get_value(value);
+                *self.registers.entry(name).or_insert(0) *= v;
+            }
+            Instruction::Mod(name, ref value) => {
+                let v = self.get_value(value);
+                *self.registers.entry(name).or_insert(0) %= v;
+            }
+            Instruction::Rcv(name) => {
+                // Without any peers, only values that were already sent can ever arrive.
+                let received = if self.senders.len() == 1 {
+                    self.receiver.try_recv().ok()
+                } else {
+                    self.receiver.recv_timeout(Duration::from_secs(1)).ok()
+                };
+
+                if let Some(v) = received {
                     *self.registers.entry(name).or_insert(0) = v;
+                } else {
+                    return Step::Blocked;
                 }
-                Instruction::Add(name, ref value) => {
-                    let v = self.get_value(value);
-                    *self.registers.entry(name).or_insert(0) += v;
-                }
-                Instruction::Mul(name, ref value) => {
-                    let v = self.get_value(value);
-                    *self.registers.entry(name).or_insert(0) *= v;
-                }
-                Instruction::Mod(name, ref value) => {
-                    let v = self.get_value(value);
-                    *self.registers.entry(name).or_insert(0) %= v;
-                }
-                Instruction::Rcv(name) => {
-                    if let Ok(v) = self.receiver.recv_timeout(Duration::from_secs(1)) {
-                        *self.registers.entry(name).or_insert(0) = v;
-                    } else {
-                        return values_sent;
-                    }
+            }
+            Instruction::Jgz(ref value, ref offset) => {
+                if self.get_value(value) > 0 {
+                    self.pc += self.get_value(offset);
+                    pc_updated = true;
                 }
-                Instruction::Jgz(ref value, ref offset) => {
-                    let v = self.get_value(value);
-                    let o = self.get_value(offset);
-
-                    if v > 0 {
-                        self.pc += o;
-                        pc_updated = true;
-                    }
+            }
+            Instruction::Jnz(ref value, ref offset) => {
+                if self.get_value(value) != 0 {
+                    self.pc += self.get_value(offset);
+                    pc_updated = true;
                 }
             }
+        }
 
-            if !pc_updated {
-                self.pc += 1;
-            }
+        if !pc_updated {
+            self.pc += 1;
+        }
+
+        step
+    }
+
+    pub fn execute(&mut self) -> u32 {
+        let mut values_sent = 0;
 
-            if self.pc < 0 || self.pc >= self.instructions.len() as i64 {
-                return values_sent;
+        loop {
+            match self.step() {
+                Step::Executed => (),
+                Step::Sent => values_sent += 1,
+                Step::Blocked | Step::Halted => return values_sent,
             }
         }
     }
@@ -209,6 +274,12 @@ pub struct Vm {
     programs: Vec<Program>,
 }
 
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Vm {
     pub fn new() -> Self {
         Vm { programs: Vec::new() }
@@ -246,6 +317,71 @@ impl Vm {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_instructions() {
+        match "jgz a -1".parse() {
+            Ok(Instruction::Jgz(Value::Register('a'), Value::Number(-1))) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn parse_unknown_instruction() {
+        let error = "jmp a 2".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(0..3, error.span());
+        assert_eq!("expected an instruction, found `jmp`", error.message());
+    }
+
+    #[test]
+    fn parse_invalid_register() {
+        let error = "set 12 a".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(4..6, error.span());
+        assert_eq!("a register", error.expected());
+    }
+
+    #[test]
+    fn parse_missing_value() {
+        let error = "add a".parse::<Instruction>().unwrap_err();
+
+        assert_eq!(
+            "expected a register or a number, found end of input",
+            error.message()
+        );
+    }
+
+    #[test]
+    fn parse_coprocessor_instructions() {
+        match "sub b -100000".parse() {
+            Ok(Instruction::Sub('b', Value::Number(-100000))) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+
+        match "jnz 1 3".parse() {
+            Ok(Instruction::Jnz(Value::Number(1), Value::Number(3))) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn count_executed_instructions() {
+        let instructions = [
+            Instruction::Set('a', Value::Number(3)),
+            Instruction::Mul('b', Value::Register('a')),
+            Instruction::Sub('a', Value::Number(1)),
+            Instruction::Jnz(Value::Register('a'), Value::Number(-2)),
+        ];
+
+        let mut program = Program::new(0, &instructions);
+
+        assert_eq!(0, program.execute());
+        assert_eq!(3, program.executed("mul"));
+        assert_eq!(3, program.executed("jnz"));
+        assert_eq!(0, program.register('a'));
+        assert_eq!(Step::Halted, program.step());
+    }
+
     #[test]
     fn get_values_sent_by_last_program() {
         let instructions = [
@@ -1,20 +1,12 @@
 extern crate day18;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day18::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
-
-    let instructions = file.lines()
-        .map(|l| {
-            l.expect("error reading file").parse().expect(
-                "unable to parse instruction",
-            )
-        })
-        .collect::<Vec<_>>();
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let instructions = input.records::<Instruction>().unwrap_or_else(|e| e.exit());
 
     let mut vm = Vm::new();
 
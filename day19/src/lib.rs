@@ -21,17 +21,11 @@ impl From<char> for Tile {
 
 impl Tile {
     fn is_horizontal_or_letter(&self) -> bool {
-        match *self {
-            Tile::Horizontal | Tile::Letter(_) => true,
-            _ => false,
-        }
+        matches!(*self, Tile::Horizontal | Tile::Letter(_))
     }
 
     fn is_vertical_or_letter(&self) -> bool {
-        match *self {
-            Tile::Vertical | Tile::Letter(_) => true,
-            _ => false,
-        }
+        matches!(*self, Tile::Vertical | Tile::Letter(_))
     }
 }
 
@@ -59,6 +53,12 @@ pub struct Map {
     position: (i32, i32),
 }
 
+impl Default for Map {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Map {
     pub fn new() -> Self {
         Map {
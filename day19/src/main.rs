@@ -1,20 +1,16 @@
 extern crate day19;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day19::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
 
     let mut map = Map::new();
 
-    for l in file.lines() {
-        let row = l.expect("error reading file")
-            .chars()
-            .map(|c| c.into())
-            .collect::<Vec<_>>();
+    for (_, l) in input.lines() {
+        let row = l.chars().map(|c| c.into()).collect::<Vec<_>>();
 
         map.add_row(&row);
     }
@@ -1,48 +1,57 @@
+extern crate input;
+
 use std::str::FromStr;
 
+use input::{ParseError, Tokens};
+
+#[derive(Debug, PartialEq)]
 pub struct Particle {
     position: (i64, i64, i64),
     velocity: (i64, i64, i64),
     acceleration: (i64, i64, i64),
 }
 
-#[derive(Debug)]
-pub struct ParticleParseError;
+fn parse_particle_component(
+    s: &str,
+    tokens: &mut Tokens,
+    expected: &str,
+) -> Result<(i64, i64, i64), ParseError> {
+    let token = tokens.expect(expected)?;
 
-fn parse_particle_component<'a, T: Iterator<Item = &'a str>>(
-    iter: &mut T,
-) -> Result<(i64, i64, i64), ParticleParseError> {
-    if let Some(s) = iter.next() {
-        let mut parts = s.trim_matches(|c: char| !c.is_numeric() && c != '-')
-            .split(',');
+    let is_delimiter = |c: char| !c.is_numeric() && c != '-';
+    let inner = token.text.trim_matches(is_delimiter);
+    let mut offset =
+        token.span.start + token.text.len() - token.text.trim_start_matches(is_delimiter).len();
 
-        let x = parts.next().unwrap_or("").parse().map_err(
-            |_| ParticleParseError,
-        )?;
+    let mut values = Vec::new();
 
-        let y = parts.next().unwrap_or("").parse().map_err(
-            |_| ParticleParseError,
-        )?;
+    for part in inner.split(',') {
+        values.push(
+            part.parse()
+                .map_err(|_| ParseError::new(s, offset..(offset + part.len()), "a number"))?,
+        );
 
-        let z = parts.next().unwrap_or("").parse().map_err(
-            |_| ParticleParseError,
-        )?;
+        offset += part.len() + 1;
+    }
 
-        Ok((x, y, z))
-    } else {
-        Err(ParticleParseError)
+    if values.len() != 3 {
+        return Err(token.error(expected));
     }
+
+    Ok((values[0], values[1], values[2]))
 }
 
 impl FromStr for Particle {
-    type Err = ParticleParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut components = s.split_whitespace();
+        let mut components = Tokens::new(s);
+
+        let position = parse_particle_component(s, &mut components, "a position vector")?;
+        let velocity = parse_particle_component(s, &mut components, "a velocity vector")?;
+        let acceleration = parse_particle_component(s, &mut components, "an acceleration vector")?;
 
-        let position = parse_particle_component(&mut components)?;
-        let velocity = parse_particle_component(&mut components)?;
-        let acceleration = parse_particle_component(&mut components)?;
+        components.end()?;
 
         Ok(Particle {
             position,
@@ -75,13 +84,19 @@ impl Particle {
 }
 
 fn distance_from_origin((x, y, z): (i64, i64, i64)) -> u64 {
-    x.abs() as u64 + y.abs() as u64 + z.abs() as u64
+    x.unsigned_abs() + y.unsigned_abs() + z.unsigned_abs()
 }
 
 pub struct ParticleSystem {
     particles: Vec<Particle>,
 }
 
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ParticleSystem {
     pub fn new() -> Self {
         ParticleSystem { particles: Vec::new() }
@@ -105,6 +120,44 @@ impl ParticleSystem {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_particle() {
+        assert_eq!(
+            Ok(Particle::new((3, 0, 0), (2, 0, 0), (-1, 0, 0))),
+            "p=<3,0,0>, v=<2,0,0>, a=<-1,0,0>".parse()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_number() {
+        let error = "p=<3,0,0>, v=<2,x,0>, a=<-1,0,0>"
+            .parse::<Particle>()
+            .unwrap_err();
+
+        assert_eq!(16..17, error.span());
+        assert_eq!("expected a number, found `x`", error.message());
+    }
+
+    #[test]
+    fn parse_missing_component() {
+        let error = "p=<3,0,0>, v=<2,0,0>".parse::<Particle>().unwrap_err();
+
+        assert_eq!(
+            "expected an acceleration vector, found end of input",
+            error.message()
+        );
+    }
+
+    #[test]
+    fn parse_short_vector() {
+        let error = "p=<3,0>, v=<2,0,0>, a=<-1,0,0>"
+            .parse::<Particle>()
+            .unwrap_err();
+
+        assert_eq!(0..8, error.span());
+        assert_eq!("a position vector", error.expected());
+    }
+
     #[test]
     fn find_closest_particle() {
         let mut particle_system = ParticleSystem::new();
@@ -1,20 +1,15 @@
 extern crate day20;
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+extern crate input;
 
 use day20::*;
+use input::Input;
 
 fn main() {
-    let file = BufReader::new(File::open("input.txt").expect("file not found"));
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
 
     let mut particle_system = ParticleSystem::new();
 
-    for l in file.lines() {
-        let particle = l.expect("error reading file").parse().expect(
-            "invlalid particle definition",
-        );
-
+    for particle in input.records().unwrap_or_else(|e| e.exit()) {
         particle_system.add_particle(particle);
     }
 
@@ -0,0 +1,341 @@
+extern crate input;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use input::{ParseError, Token, Tokens};
+
+const START: &str = ".#./..#/###";
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    size: usize,
+    pixels: Vec<bool>,
+}
+
+impl Pattern {
+    pub fn start() -> Self {
+        START.parse().unwrap()
+    }
+
+    fn blank(size: usize) -> Self {
+        Pattern {
+            size,
+            pixels: vec![false; size * size],
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn pixels_on(&self) -> usize {
+        self.pixels.iter().filter(|&&p| p).count()
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.pixels[y * self.size + x]
+    }
+
+    fn set(&mut self, x: usize, y: usize, value: bool) {
+        self.pixels[y * self.size + x] = value;
+    }
+
+    fn transform<F: Fn(usize, usize) -> (usize, usize)>(&self, f: F) -> Self {
+        let mut pattern = Pattern::blank(self.size);
+
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let (from_x, from_y) = f(x, y);
+                pattern.set(x, y, self.get(from_x, from_y));
+            }
+        }
+
+        pattern
+    }
+
+    fn rotate(&self) -> Self {
+        let last = self.size - 1;
+
+        self.transform(|x, y| (y, last - x))
+    }
+
+    fn flip(&self) -> Self {
+        let last = self.size - 1;
+
+        self.transform(|x, y| (last - x, y))
+    }
+
+    pub fn variants(&self) -> Vec<Pattern> {
+        let mut variants = Vec::new();
+        let mut pattern = self.clone();
+
+        for _ in 0..4 {
+            for variant in [pattern.clone(), pattern.flip()] {
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+            }
+
+            pattern = pattern.rotate();
+        }
+
+        variants
+    }
+
+    fn block(&self, size: usize, block_x: usize, block_y: usize) -> Self {
+        let mut block = Pattern::blank(size);
+
+        for y in 0..size {
+            for x in 0..size {
+                block.set(x, y, self.get(block_x * size + x, block_y * size + y));
+            }
+        }
+
+        block
+    }
+
+    fn paste(&mut self, block: &Pattern, block_x: usize, block_y: usize) {
+        for y in 0..block.size {
+            for x in 0..block.size {
+                self.set(
+                    block_x * block.size + x,
+                    block_y * block.size + y,
+                    block.get(x, y),
+                );
+            }
+        }
+    }
+}
+
+fn parse_pattern(s: &str, token: &Token) -> Result<Pattern, ParseError> {
+    let rows = token.text.split('/').collect::<Vec<_>>();
+    let size = rows.len();
+
+    let mut pixels = Vec::new();
+    let mut offset = token.span.start;
+
+    for row in rows {
+        if row.len() != size {
+            let span = offset..(offset + row.len());
+            return Err(ParseError::new(
+                s,
+                span,
+                &format!("a row of {} pixels", size),
+            ));
+        }
+
+        for (i, c) in row.char_indices() {
+            match c {
+                '.' => pixels.push(false),
+                '#' => pixels.push(true),
+                _ => {
+                    let span = (offset + i)..(offset + i + c.len_utf8());
+                    return Err(ParseError::new(s, span, "`.` or `#`"));
+                }
+            }
+        }
+
+        offset += row.len() + 1;
+    }
+
+    Ok(Pattern { size, pixels })
+}
+
+impl FromStr for Pattern {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokens::new(s);
+        let pattern = parse_pattern(s, &tokens.expect("a pattern")?)?;
+
+        tokens.end()?;
+
+        Ok(pattern)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = self
+            .pixels
+            .chunks(self.size)
+            .map(|row| {
+                row.iter()
+                    .map(|&p| if p { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", rows.join("/"))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rule {
+    pub from: Pattern,
+    pub to: Pattern,
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokens::new(s);
+
+        let from_token = tokens.expect("a pattern")?;
+        let from = parse_pattern(s, &from_token)?;
+
+        if from.size != 2 && from.size != 3 {
+            return Err(from_token.error("a 2x2 or 3x3 pattern"));
+        }
+
+        let arrow = tokens.expect("`=>`")?;
+
+        if arrow.text != "=>" {
+            return Err(arrow.error("`=>`"));
+        }
+
+        let to_token = tokens.expect("an enhanced pattern")?;
+        let to = parse_pattern(s, &to_token)?;
+
+        if to.size != from.size + 1 {
+            let expected = format!("a {0}x{0} pattern", from.size + 1);
+            return Err(to_token.error(&expected));
+        }
+
+        tokens.end()?;
+
+        Ok(Rule { from, to })
+    }
+}
+
+pub struct Rulebook {
+    rules: HashMap<Pattern, Pattern>,
+}
+
+impl Default for Rulebook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rulebook {
+    pub fn new() -> Self {
+        Rulebook {
+            rules: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, rule: Rule) {
+        for variant in rule.from.variants() {
+            self.rules.insert(variant, rule.to.clone());
+        }
+    }
+
+    pub fn enhance(&self, pattern: &Pattern) -> Result<Pattern, Pattern> {
+        let size = if pattern.size.is_multiple_of(2) { 2 } else { 3 };
+        let blocks = pattern.size / size;
+
+        let mut enhanced = Pattern::blank(blocks * (size + 1));
+
+        for block_y in 0..blocks {
+            for block_x in 0..blocks {
+                let block = pattern.block(size, block_x, block_y);
+
+                match self.rules.get(&block) {
+                    Some(replacement) => enhanced.paste(replacement, block_x, block_y),
+                    None => return Err(block),
+                }
+            }
+        }
+
+        Ok(enhanced)
+    }
+
+    pub fn pixels_on_after(&self, iterations: usize) -> Result<usize, Pattern> {
+        let mut pattern = Pattern::start();
+
+        for _ in 0..iterations {
+            pattern = self.enhance(&pattern)?;
+        }
+
+        Ok(pattern.pixels_on())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Rulebook {
+        let mut rulebook = Rulebook::new();
+
+        rulebook.add("../.# => ##./#../...".parse().unwrap());
+        rulebook.add(".#./..#/### => #..#/..../..../#..#".parse().unwrap());
+
+        rulebook
+    }
+
+    #[test]
+    fn parse_and_display_pattern() {
+        let pattern = ".#./..#/###".parse::<Pattern>().unwrap();
+
+        assert_eq!(3, pattern.size());
+        assert_eq!(5, pattern.pixels_on());
+        assert_eq!(".#./..#/###", pattern.to_string());
+    }
+
+    #[test]
+    fn all_variants() {
+        assert_eq!(8, Pattern::start().variants().len());
+        assert_eq!(4, "#./..".parse::<Pattern>().unwrap().variants().len());
+        assert_eq!(1, "##/##".parse::<Pattern>().unwrap().variants().len());
+    }
+
+    #[test]
+    fn rotate_pattern() {
+        assert_eq!("#../#.#/##.", Pattern::start().rotate().to_string());
+    }
+
+    #[test]
+    fn enhance_example() {
+        let rulebook = example();
+
+        let once = rulebook.enhance(&Pattern::start()).unwrap();
+
+        assert_eq!("#..#/..../..../#..#", once.to_string());
+        assert_eq!(
+            "##.##./#..#../....../##.##./#..#../......",
+            rulebook.enhance(&once).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn count_pixels_after_two_iterations() {
+        assert_eq!(Ok(12), example().pixels_on_after(2));
+    }
+
+    #[test]
+    fn report_missing_rule() {
+        let error = example().pixels_on_after(3).unwrap_err();
+
+        assert_eq!("##/#.", error.to_string());
+    }
+
+    #[test]
+    fn parse_invalid_rules() {
+        let error = "../.x => ##./#../...".parse::<Rule>().unwrap_err();
+        assert_eq!(4..5, error.span());
+
+        let error = "../.# -> ##./#../...".parse::<Rule>().unwrap_err();
+        assert_eq!("expected `=>`, found `->`", error.message());
+
+        let error = "../.# => ##/#.".parse::<Rule>().unwrap_err();
+        assert_eq!("expected a 3x3 pattern, found `##/#.`", error.message());
+
+        let error = "../.## => ##./#../...".parse::<Rule>().unwrap_err();
+        assert_eq!("expected a row of 2 pixels, found `.##`", error.message());
+    }
+}
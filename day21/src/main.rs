@@ -0,0 +1,27 @@
+extern crate day21;
+extern crate input;
+
+use std::process;
+
+use day21::*;
+use input::Input;
+
+fn main() {
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+
+    let mut rulebook = Rulebook::new();
+
+    for rule in input.records().unwrap_or_else(|e| e.exit()) {
+        rulebook.add(rule);
+    }
+
+    for &iterations in &[5, 18] {
+        match rulebook.pixels_on_after(iterations) {
+            Ok(pixels) => println!("Pixels on after {} iterations: {}", iterations, pixels),
+            Err(pattern) => {
+                eprintln!("{}: no enhancement rule for {}", input.name(), pattern);
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+extern crate input;
+
+use std::str::FromStr;
+
+use input::ParseError;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Node {
+    Clean,
+    Weakened,
+    Infected,
+    Flagged,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rules {
+    Simple,
+    Evolved,
+}
+
+impl Rules {
+    fn next(self, node: Node) -> Node {
+        match (self, node) {
+            (Rules::Simple, Node::Clean) => Node::Infected,
+            (Rules::Simple, _) => Node::Clean,
+            (Rules::Evolved, Node::Clean) => Node::Weakened,
+            (Rules::Evolved, Node::Weakened) => Node::Infected,
+            (Rules::Evolved, Node::Infected) => Node::Flagged,
+            (Rules::Evolved, Node::Flagged) => Node::Clean,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Row(pub Vec<Node>);
+
+impl FromStr for Row {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.char_indices()
+            .map(|(i, c)| match c {
+                '.' => Ok(Node::Clean),
+                '#' => Ok(Node::Infected),
+                _ => Err(ParseError::new(s, i..(i + c.len_utf8()), "`.` or `#`")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Row)
+    }
+}
+
+// A square grid centred on the origin that doubles in size whenever the carrier walks off
+// its edge, which is much faster than hashing positions for millions of bursts.
+struct Grid {
+    side: i64,
+    offset: i64,
+    nodes: Vec<Node>,
+}
+
+impl Grid {
+    fn new(side: i64) -> Self {
+        Grid {
+            side,
+            offset: side / 2,
+            nodes: vec![Node::Clean; (side * side) as usize],
+        }
+    }
+
+    fn contains(&self, (x, y): (i64, i64)) -> bool {
+        let (x, y) = (x + self.offset, y + self.offset);
+
+        x >= 0 && y >= 0 && x < self.side && y < self.side
+    }
+
+    fn grow(&mut self) {
+        let mut grown = Grid::new(self.side * 2);
+        let shift = grown.offset - self.offset;
+
+        for y in 0..self.side {
+            let from = (y * self.side) as usize;
+            let to = ((y + shift) * grown.side + shift) as usize;
+
+            grown.nodes[to..(to + self.side as usize)]
+                .copy_from_slice(&self.nodes[from..(from + self.side as usize)]);
+        }
+
+        *self = grown;
+    }
+
+    fn get_mut(&mut self, position: (i64, i64)) -> &mut Node {
+        while !self.contains(position) {
+            self.grow();
+        }
+
+        let (x, y) = (position.0 + self.offset, position.1 + self.offset);
+
+        &mut self.nodes[(y * self.side + x) as usize]
+    }
+}
+
+pub struct Cluster {
+    grid: Grid,
+    position: (i64, i64),
+    direction: (i64, i64),
+    infections: usize,
+}
+
+impl Cluster {
+    pub fn new(rows: &[Row]) -> Self {
+        let height = rows.len() as i64;
+        let width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0) as i64;
+
+        let mut grid = Grid::new((width.max(height) + 1).max(16));
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &node) in row.0.iter().enumerate() {
+                *grid.get_mut((x as i64 - width / 2, y as i64 - height / 2)) = node;
+            }
+        }
+
+        Cluster {
+            grid,
+            position: (0, 0),
+            direction: (0, -1),
+            infections: 0,
+        }
+    }
+
+    pub fn node(&mut self, x: i64, y: i64) -> Node {
+        *self.grid.get_mut((x, y))
+    }
+
+    pub fn infections(&self) -> usize {
+        self.infections
+    }
+
+    pub fn burst(&mut self, rules: Rules) {
+        let node = self.grid.get_mut(self.position);
+        let (dx, dy) = self.direction;
+
+        self.direction = match *node {
+            Node::Clean => (dy, -dx),
+            Node::Weakened => (dx, dy),
+            Node::Infected => (-dy, dx),
+            Node::Flagged => (-dx, -dy),
+        };
+
+        *node = rules.next(*node);
+
+        if *node == Node::Infected {
+            self.infections += 1;
+        }
+
+        self.position = (
+            self.position.0 + self.direction.0,
+            self.position.1 + self.direction.1,
+        );
+    }
+
+    pub fn infections_after(&mut self, bursts: usize, rules: Rules) -> usize {
+        for _ in 0..bursts {
+            self.burst(rules);
+        }
+
+        self.infections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Cluster {
+        let rows = ["..#", "#..", "..."]
+            .iter()
+            .map(|r| r.parse().unwrap())
+            .collect::<Vec<_>>();
+
+        Cluster::new(&rows)
+    }
+
+    #[test]
+    fn parse_row() {
+        assert_eq!(
+            Row(vec![Node::Clean, Node::Clean, Node::Infected]),
+            "..#".parse().unwrap()
+        );
+
+        let error = "..x".parse::<Row>().unwrap_err();
+
+        assert_eq!(2..3, error.span());
+    }
+
+    #[test]
+    fn start_in_the_middle() {
+        let mut cluster = example();
+
+        assert_eq!(Node::Infected, cluster.node(-1, 0));
+        assert_eq!(Node::Infected, cluster.node(1, -1));
+        assert_eq!(Node::Clean, cluster.node(0, 0));
+    }
+
+    #[test]
+    fn simple_bursts() {
+        assert_eq!(5, example().infections_after(7, Rules::Simple));
+        assert_eq!(41, example().infections_after(70, Rules::Simple));
+        assert_eq!(5587, example().infections_after(10_000, Rules::Simple));
+    }
+
+    #[test]
+    fn evolved_bursts() {
+        assert_eq!(26, example().infections_after(100, Rules::Evolved));
+        assert_eq!(
+            2_511_944,
+            example().infections_after(10_000_000, Rules::Evolved)
+        );
+    }
+
+    #[test]
+    fn grow_keeps_nodes() {
+        let mut cluster = example();
+
+        *cluster.grid.get_mut((100, -100)) = Node::Flagged;
+
+        assert_eq!(Node::Infected, cluster.node(-1, 0));
+        assert_eq!(Node::Flagged, cluster.node(100, -100));
+    }
+}
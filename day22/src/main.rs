@@ -0,0 +1,19 @@
+extern crate day22;
+extern crate input;
+
+use day22::*;
+use input::Input;
+
+fn main() {
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let rows = input.records().unwrap_or_else(|e| e.exit());
+
+    println!(
+        "Infections after 10000 bursts: {}",
+        Cluster::new(&rows).infections_after(10_000, Rules::Simple)
+    );
+    println!(
+        "Infections after 10000000 evolved bursts: {}",
+        Cluster::new(&rows).infections_after(10_000_000, Rules::Evolved)
+    );
+}
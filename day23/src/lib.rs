@@ -0,0 +1,228 @@
+extern crate day18;
+
+use day18::{Instruction, Program, Step, Value};
+
+pub struct Coprocessor {
+    program: Program,
+}
+
+impl Coprocessor {
+    pub fn new(instructions: &[Instruction]) -> Self {
+        Coprocessor {
+            program: Program::new(0, instructions),
+        }
+    }
+
+    pub fn debug_mode(mut self, enabled: bool) -> Self {
+        self.program.set_register('a', if enabled { 0 } else { 1 });
+        self
+    }
+
+    pub fn register(&self, name: char) -> i64 {
+        self.program.register(name)
+    }
+
+    pub fn executed(&self, name: &str) -> u64 {
+        self.program.executed(name)
+    }
+
+    // Nothing ever sends the coprocessor a value, so the first `rcv` stops it for good.
+    pub fn run(&mut self) {
+        while let Step::Executed | Step::Sent = self.program.step() {}
+    }
+
+    fn run_until(&mut self, pc: i64) -> bool {
+        while self.program.pc() != pc {
+            if let Step::Blocked | Step::Halted = self.program.step() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+pub fn count_mul(instructions: &[Instruction]) -> u64 {
+    let mut coprocessor = Coprocessor::new(instructions).debug_mode(true);
+
+    coprocessor.run();
+    coprocessor.executed("mul")
+}
+
+fn is_set(instruction: &Instruction, register: char, value: i64) -> bool {
+    match *instruction {
+        Instruction::Set(r, Value::Number(v)) => r == register && v == value,
+        _ => false,
+    }
+}
+
+fn is_composite(n: i64) -> bool {
+    n > 3 && (2..).take_while(|d| d * d <= n).any(|d| n % d == 0)
+}
+
+// The program counts the composite numbers from `b` to `c` in steps of a constant, testing
+// each candidate by trying every product `d * e`. After the setup code has run, the whole
+// outer loop can be replaced by a direct count.
+fn count_composites(instructions: &[Instruction]) -> Option<i64> {
+    let head = (0..instructions.len().saturating_sub(2)).find(|&i| {
+        is_set(&instructions[i], 'f', 1)
+            && is_set(&instructions[i + 1], 'd', 2)
+            && is_set(&instructions[i + 2], 'e', 2)
+    })?;
+
+    let step = instructions[head..]
+        .iter()
+        .rev()
+        .find_map(|i| match *i {
+            Instruction::Sub('b', Value::Number(n)) => n.checked_neg(),
+            _ => None,
+        })
+        .filter(|&step| step > 0)?;
+
+    let counts_composites = instructions[head..]
+        .iter()
+        .any(|i| matches!(*i, Instruction::Sub('h', Value::Number(-1))));
+
+    if !counts_composites {
+        return None;
+    }
+
+    let mut coprocessor = Coprocessor::new(instructions).debug_mode(false);
+
+    if !coprocessor.run_until(head as i64) {
+        return None;
+    }
+
+    let (from, to) = (coprocessor.register('b'), coprocessor.register('c'));
+
+    if from > to {
+        return None;
+    }
+
+    let composites = (from..=to)
+        .step_by(step as usize)
+        .filter(|&n| is_composite(n))
+        .count();
+
+    Some(coprocessor.register('h') + composites as i64)
+}
+
+pub fn register_h(instructions: &[Instruction], optimize: bool) -> i64 {
+    if optimize {
+        if let Some(h) = count_composites(instructions) {
+            return h;
+        }
+    }
+
+    let mut coprocessor = Coprocessor::new(instructions).debug_mode(false);
+
+    coprocessor.run();
+    coprocessor.register('h')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "set b 57
+set c b
+jnz a 2
+jnz 1 5
+mul b 100
+sub b -100000
+set c b
+sub c -17000
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -17
+jnz 1 -23";
+
+    fn program(source: &str) -> Vec<Instruction> {
+        source.lines().map(|l| l.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn count_mul_in_debug_mode() {
+        assert_eq!(3025, count_mul(&program(PROGRAM)));
+    }
+
+    #[test]
+    fn optimized_register_h() {
+        assert_eq!(915, register_h(&program(PROGRAM), true));
+    }
+
+    #[test]
+    fn optimization_matches_interpreter() {
+        let small = PROGRAM
+            .replace("mul b 100", "mul b 1")
+            .replace("sub b -100000", "sub b -10")
+            .replace("sub c -17000", "sub c -170");
+
+        let instructions = program(&small);
+
+        assert_eq!(
+            Some(register_h(&instructions, false)),
+            count_composites(&instructions)
+        );
+        assert_eq!(9, register_h(&instructions, true));
+    }
+
+    #[test]
+    fn fall_back_to_interpreter() {
+        let instructions = program("set h 3\nsub h -1");
+
+        assert_eq!(None, count_composites(&instructions));
+        assert_eq!(4, register_h(&instructions, true));
+    }
+
+    #[test]
+    fn fall_back_without_a_forward_step() {
+        let stuck = PROGRAM
+            .replace("mul b 100", "mul b 1")
+            .replace("sub b -100000", "sub b -11")
+            .replace("sub c -17000", "sub c 0")
+            .replace("sub b -17", "sub b 0");
+
+        let instructions = program(&stuck);
+
+        assert_eq!(None, count_composites(&instructions));
+        assert_eq!(1, register_h(&instructions, true));
+    }
+
+    #[test]
+    fn stop_at_receive() {
+        let mut coprocessor = Coprocessor::new(&program("set b 1\nrcv b\nset b 2"));
+
+        coprocessor.run();
+
+        assert_eq!(1, coprocessor.register('b'));
+        assert_eq!(1, coprocessor.executed("set"));
+    }
+
+    #[test]
+    fn detect_composites() {
+        let composites = (0..20).filter(|&n| is_composite(n)).collect::<Vec<_>>();
+
+        assert_eq!(vec![4, 6, 8, 9, 10, 12, 14, 15, 16, 18], composites);
+    }
+}
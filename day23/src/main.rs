@@ -0,0 +1,15 @@
+extern crate day18;
+extern crate day23;
+extern crate input;
+
+use day18::Instruction;
+use day23::*;
+use input::Input;
+
+fn main() {
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let instructions = input.records::<Instruction>().unwrap_or_else(|e| e.exit());
+
+    println!("Mul instructions executed: {}", count_mul(&instructions));
+    println!("Value of register h: {}", register_h(&instructions, true));
+}
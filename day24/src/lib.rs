@@ -0,0 +1,382 @@
+extern crate input;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use input::ParseError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Component {
+    pub a: u32,
+    pub b: u32,
+}
+
+impl Component {
+    pub fn new(a: u32, b: u32) -> Self {
+        Component { a, b }
+    }
+
+    pub fn strength(&self) -> u32 {
+        self.a + self.b
+    }
+
+    fn other_port(&self, port: u32) -> u32 {
+        if self.a == port {
+            self.b
+        } else {
+            self.a
+        }
+    }
+}
+
+impl FromStr for Component {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let start = s.len() - s.trim_start().len();
+        let end = s.trim_end().len().max(start);
+
+        let slash = s[start..end]
+            .find('/')
+            .map(|i| start + i)
+            .ok_or_else(|| ParseError::new(s, end..end, "`/`"))?;
+
+        let port = |from: usize, to: usize| {
+            s[from..to]
+                .parse()
+                .map_err(|_| ParseError::new(s, from..to, "a port"))
+        };
+
+        Ok(Component::new(port(start, slash)?, port(slash + 1, end)?))
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.a, self.b)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bridge {
+    pub components: Vec<Component>,
+    pub strength: u32,
+}
+
+impl Bridge {
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+impl fmt::Display for Bridge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let components = self
+            .components
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", components.join("--"))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Bridges {
+    pub strongest: Bridge,
+    pub longest: Bridge,
+}
+
+struct Search<'a> {
+    components: &'a [Component],
+    by_port: HashMap<u32, Vec<usize>>,
+    used: Vec<bool>,
+    path: Vec<Component>,
+    strength: u32,
+    remaining_strength: u32,
+    remaining: usize,
+    strongest: Bridge,
+    longest: Bridge,
+}
+
+impl<'a> Search<'a> {
+    fn new(components: &'a [Component]) -> Self {
+        let mut by_port = HashMap::new();
+
+        for (i, c) in components.iter().enumerate() {
+            by_port.entry(c.a).or_insert_with(Vec::new).push(i);
+
+            if c.b != c.a {
+                by_port.entry(c.b).or_insert_with(Vec::new).push(i);
+            }
+        }
+
+        Search {
+            components,
+            by_port,
+            used: vec![false; components.len()],
+            path: Vec::new(),
+            strength: 0,
+            remaining_strength: components.iter().map(|c| c.strength()).sum(),
+            remaining: components.len(),
+            strongest: Bridge::default(),
+            longest: Bridge::default(),
+        }
+    }
+
+    fn bridge(&self) -> Bridge {
+        Bridge {
+            components: self.path.clone(),
+            strength: self.strength,
+        }
+    }
+
+    fn record(&mut self) {
+        if self.strength > self.strongest.strength {
+            self.strongest = self.bridge();
+        }
+
+        if (self.path.len(), self.strength) > (self.longest.len(), self.longest.strength) {
+            self.longest = self.bridge();
+        }
+    }
+
+    // Even using every unused component can neither beat the strongest bridge nor the
+    // longest one, so there is no point in extending this bridge any further.
+    fn can_improve(&self) -> bool {
+        let max_strength = self.strength + self.remaining_strength;
+        let max_length = self.path.len() + self.remaining;
+
+        max_strength > self.strongest.strength
+            || max_length > self.longest.len()
+            || (max_length == self.longest.len() && max_strength > self.longest.strength)
+    }
+
+    fn connect(&mut self, index: usize, port: u32) {
+        let component = self.components[index];
+        let next = component.other_port(port);
+
+        self.used[index] = true;
+        self.path.push(Component::new(port, next));
+        self.strength += component.strength();
+        self.remaining_strength -= component.strength();
+        self.remaining -= 1;
+
+        self.visit(next);
+
+        self.used[index] = false;
+        self.path.pop();
+        self.strength -= component.strength();
+        self.remaining_strength += component.strength();
+        self.remaining += 1;
+    }
+
+    fn visit(&mut self, port: u32) {
+        self.record();
+
+        if !self.can_improve() {
+            return;
+        }
+
+        let candidates = self
+            .by_port
+            .get(&port)
+            .map(|c| {
+                c.iter()
+                    .cloned()
+                    .filter(|&i| !self.used[i])
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        // A component with the same port on both ends only ever makes a bridge longer and
+        // stronger without changing where it ends, so it is always worth taking first.
+        if let Some(&double) = candidates
+            .iter()
+            .find(|&&i| self.components[i].a == self.components[i].b)
+        {
+            self.connect(double, port);
+            return;
+        }
+
+        for index in candidates {
+            self.connect(index, port);
+        }
+    }
+}
+
+pub fn build_bridges(components: &[Component]) -> Bridges {
+    let mut search = Search::new(components);
+
+    search.visit(0);
+
+    Bridges {
+        strongest: search.strongest,
+        longest: search.longest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tries every bridge without any pruning, returning the highest strength and the
+    // longest length along with the highest strength at that length.
+    fn brute_force(
+        components: &[Component],
+        used: &mut [bool],
+        port: u32,
+        length: usize,
+        strength: u32,
+    ) -> (u32, (usize, u32)) {
+        let mut best = (strength, (length, strength));
+
+        for i in 0..components.len() {
+            let c = components[i];
+
+            if used[i] || (c.a != port && c.b != port) {
+                continue;
+            }
+
+            used[i] = true;
+            let next = c.other_port(port);
+            let (strongest, longest) =
+                brute_force(components, used, next, length + 1, strength + c.strength());
+            used[i] = false;
+
+            best = (best.0.max(strongest), best.1.max(longest));
+        }
+
+        best
+    }
+
+    fn is_valid(bridge: &Bridge, components: &[Component]) -> bool {
+        let mut unused = components.to_vec();
+        let mut port = 0;
+
+        for c in &bridge.components {
+            match unused
+                .iter()
+                .position(|u| *u == *c || *u == Component::new(c.b, c.a))
+            {
+                Some(i) if c.a == port => {
+                    unused.swap_remove(i);
+                    port = c.b;
+                }
+                _ => return false,
+            }
+        }
+
+        bridge.strength
+            == bridge
+                .components
+                .iter()
+                .map(Component::strength)
+                .sum::<u32>()
+    }
+
+    fn example() -> Vec<Component> {
+        ["0/2", "2/2", "2/3", "3/4", "3/5", "0/1", "10/1", "9/10"]
+            .iter()
+            .map(|c| c.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parse_component() {
+        assert_eq!(Component::new(10, 1), " 10/1 ".parse().unwrap());
+
+        let error = "10-1".parse::<Component>().unwrap_err();
+        assert_eq!("expected `/`, found end of input", error.message());
+
+        let error = "10/x".parse::<Component>().unwrap_err();
+        assert_eq!(3..4, error.span());
+    }
+
+    #[test]
+    fn strongest_bridge() {
+        let bridges = build_bridges(&example());
+
+        assert_eq!(31, bridges.strongest.strength);
+        assert_eq!("0/1--1/10--10/9", bridges.strongest.to_string());
+    }
+
+    #[test]
+    fn strongest_longest_bridge() {
+        let bridges = build_bridges(&example());
+
+        assert_eq!(19, bridges.longest.strength);
+        assert_eq!("0/2--2/2--2/3--3/5", bridges.longest.to_string());
+    }
+
+    #[test]
+    fn no_starting_component() {
+        let bridges = build_bridges(&[Component::new(1, 2)]);
+
+        assert!(bridges.strongest.is_empty());
+        assert_eq!(0, bridges.longest.strength);
+    }
+
+    #[test]
+    fn larger_set_of_components() {
+        let components = (0..56)
+            .map(|i| Component::new((i * 17 + 5) % 50, (i * 31 + 11) % 50))
+            .chain(Some(Component::new(0, 5)))
+            .collect::<Vec<_>>();
+
+        let bridges = build_bridges(&components);
+        let mut used = vec![false; components.len()];
+        let (strongest, longest) = brute_force(&components, &mut used, 0, 0, 0);
+
+        assert_eq!(strongest, bridges.strongest.strength);
+        assert_eq!(longest, (bridges.longest.len(), bridges.longest.strength));
+        assert!(is_valid(&bridges.strongest, &components));
+        assert!(is_valid(&bridges.longest, &components));
+    }
+
+    // Many small random sets, each compared against a search without any pruning, including
+    // sets where the strongest bridge is not the longest one.
+    #[test]
+    fn pruning_matches_brute_force() {
+        let mut state = 0x2545_f491u32;
+        let mut port = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state % 7
+        };
+
+        let mut diverging = 0;
+
+        for _ in 0..300 {
+            let components = (0..12)
+                .map(|_| Component::new(port(), port()))
+                .collect::<Vec<_>>();
+
+            let bridges = build_bridges(&components);
+            let mut used = vec![false; components.len()];
+            let (strongest, longest) = brute_force(&components, &mut used, 0, 0, 0);
+
+            assert_eq!(strongest, bridges.strongest.strength, "{:?}", components);
+            assert_eq!(
+                longest,
+                (bridges.longest.len(), bridges.longest.strength),
+                "{:?}",
+                components
+            );
+            assert!(is_valid(&bridges.strongest, &components));
+            assert!(is_valid(&bridges.longest, &components));
+
+            if bridges.strongest.strength > bridges.longest.strength {
+                diverging += 1;
+            }
+        }
+
+        assert!(diverging > 0);
+    }
+}
@@ -0,0 +1,21 @@
+extern crate day24;
+extern crate input;
+
+use day24::*;
+use input::Input;
+
+fn main() {
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let components = input.records::<Component>().unwrap_or_else(|e| e.exit());
+
+    let bridges = build_bridges(&components);
+
+    println!(
+        "Strongest bridge: {} ({})",
+        bridges.strongest.strength, bridges.strongest
+    );
+    println!(
+        "Strongest of the longest bridges: {} ({})",
+        bridges.longest.strength, bridges.longest
+    );
+}
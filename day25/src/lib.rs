@@ -0,0 +1,578 @@
+extern crate input;
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use input::{Input, ParseError, Tokens};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Action {
+    pub write: bool,
+    pub movement: Move,
+    pub next: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Blueprint {
+    start: usize,
+    steps: u64,
+    names: Vec<String>,
+    actions: Vec<Action>,
+}
+
+struct Name {
+    text: String,
+    span: Range<usize>,
+}
+
+fn keywords(tokens: &mut Tokens, words: &[&str]) -> Result<(), ParseError> {
+    for word in words {
+        let expected = format!("`{}`", word);
+        let token = tokens.expect(&expected)?;
+
+        if token.text != *word {
+            return Err(token.error(&expected));
+        }
+    }
+
+    Ok(())
+}
+
+fn terminated<'a>(
+    s: &'a str,
+    tokens: &mut Tokens<'a>,
+    terminator: char,
+    expected: &str,
+) -> Result<(&'a str, Range<usize>), ParseError> {
+    let expected = format!("{} followed by `{}`", expected, terminator);
+    let token = tokens.expect(&expected)?;
+
+    if !token.text.ends_with(terminator) || token.text.len() == 1 {
+        return Err(token.error(&expected));
+    }
+
+    tokens.end()?;
+
+    let span = token.span.start..(token.span.end - terminator.len_utf8());
+
+    Ok((&s[span.clone()], span))
+}
+
+fn name<'a>(s: &'a str, tokens: &mut Tokens<'a>, terminator: char) -> Result<Name, ParseError> {
+    let (text, span) = terminated(s, tokens, terminator, "a state name")?;
+
+    Ok(Name {
+        text: text.to_owned(),
+        span,
+    })
+}
+
+fn value<'a>(
+    s: &'a str,
+    tokens: &mut Tokens<'a>,
+    terminator: char,
+) -> Result<(bool, Range<usize>), ParseError> {
+    match terminated(s, tokens, terminator, "`0` or `1`")? {
+        ("0", span) => Ok((false, span)),
+        ("1", span) => Ok((true, span)),
+        (_, span) => Err(ParseError::new(s, span, "`0` or `1`")),
+    }
+}
+
+fn begin(s: &str) -> Result<Name, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(&mut tokens, &["Begin", "in", "state"])?;
+    name(s, &mut tokens, '.')
+}
+
+fn checksum(s: &str) -> Result<u64, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(
+        &mut tokens,
+        &["Perform", "a", "diagnostic", "checksum", "after"],
+    )?;
+    let steps = tokens
+        .expect("a number of steps")?
+        .parse("a number of steps")?;
+    keywords(&mut tokens, &["steps."])?;
+    tokens.end()?;
+
+    Ok(steps)
+}
+
+fn state(s: &str) -> Result<Name, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(&mut tokens, &["In", "state"])?;
+    name(s, &mut tokens, ':')
+}
+
+fn condition(s: &str) -> Result<(bool, Range<usize>), ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(&mut tokens, &["If", "the", "current", "value", "is"])?;
+    value(s, &mut tokens, ':')
+}
+
+fn write(s: &str) -> Result<bool, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(&mut tokens, &["-", "Write", "the", "value"])?;
+    value(s, &mut tokens, '.').map(|(value, _)| value)
+}
+
+fn movement(s: &str) -> Result<Move, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(&mut tokens, &["-", "Move", "one", "slot", "to", "the"])?;
+
+    match terminated(s, &mut tokens, '.', "`left` or `right`")? {
+        ("left", _) => Ok(Move::Left),
+        ("right", _) => Ok(Move::Right),
+        (_, span) => Err(ParseError::new(s, span, "`left` or `right`")),
+    }
+}
+
+fn next_state(s: &str) -> Result<Name, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    keywords(&mut tokens, &["-", "Continue", "with", "state"])?;
+    name(s, &mut tokens, '.')
+}
+
+// States may be referred to before they are defined, so names are numbered in order of
+// appearance and every reference is only checked once the whole blueprint has been read.
+struct Parser<'a> {
+    input: &'a Input,
+    lines: Vec<(usize, &'a str)>,
+    position: usize,
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+    states: Vec<Option<[Action; 2]>>,
+    references: Vec<(usize, &'a str, Name)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a Input) -> Self {
+        Parser {
+            input,
+            lines: input
+                .lines()
+                .filter(|&(_, l)| !l.trim().is_empty())
+                .collect(),
+            position: 0,
+            names: Vec::new(),
+            indices: HashMap::new(),
+            states: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.position == self.lines.len()
+    }
+
+    fn line<T, F>(&mut self, expected: &str, parse: F) -> Result<(usize, &'a str, T), input::Error>
+    where
+        F: Fn(&str) -> Result<T, ParseError>,
+    {
+        let (number, l) = match self.lines.get(self.position) {
+            Some(&line) => line,
+            None => {
+                let (number, l) = self.lines.last().cloned().unwrap_or((1, ""));
+                return Err(self.input.syntax(number, ParseError::at_end(l, expected)));
+            }
+        };
+
+        self.position += 1;
+
+        let value = parse(l).map_err(|e| self.input.syntax(number, e))?;
+
+        Ok((number, l, value))
+    }
+
+    fn index(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+
+        self.names.push(name.to_owned());
+        self.states.push(None);
+        self.indices.insert(name.to_owned(), self.names.len() - 1);
+
+        self.names.len() - 1
+    }
+
+    fn reference(&mut self, number: usize, l: &'a str, name: Name) -> usize {
+        let index = self.index(&name.text);
+
+        self.references.push((number, l, name));
+
+        index
+    }
+
+    fn action(&mut self, value: bool) -> Result<Action, input::Error> {
+        let expected = format!("`If the current value is {}:`", u8::from(value));
+        let (number, l, (current, span)) = self.line(&expected, condition)?;
+
+        if current != value {
+            let error = ParseError::new(l, span, &format!("`{}`", u8::from(value)));
+            return Err(self.input.syntax(number, error));
+        }
+
+        let (_, _, write) = self.line("`- Write the value <value>.`", write)?;
+        let (_, _, movement) = self.line("`- Move one slot to the <direction>.`", movement)?;
+        let (number, l, name) = self.line("`- Continue with state <name>.`", next_state)?;
+
+        Ok(Action {
+            write,
+            movement,
+            next: self.reference(number, l, name),
+        })
+    }
+
+    fn state(&mut self) -> Result<(), input::Error> {
+        let (number, l, name) = self.line("`In state <name>:`", state)?;
+        let index = self.index(&name.text);
+
+        if self.states[index].is_some() {
+            let error = ParseError::new(l, name.span, "a state that is not defined yet");
+            return Err(self.input.syntax(number, error));
+        }
+
+        let actions = [self.action(false)?, self.action(true)?];
+        self.states[index] = Some(actions);
+
+        Ok(())
+    }
+
+    fn finish(self, start: usize, steps: u64) -> Result<Blueprint, input::Error> {
+        for &(number, l, ref name) in &self.references {
+            if self.states[self.indices[&name.text]].is_none() {
+                let error = ParseError::new(l, name.span.clone(), "a defined state");
+                return Err(self.input.syntax(number, error));
+            }
+        }
+
+        Ok(Blueprint {
+            start,
+            steps,
+            names: self.names,
+            actions: self
+                .states
+                .into_iter()
+                .flat_map(|s| s.unwrap().to_vec())
+                .collect(),
+        })
+    }
+}
+
+impl Blueprint {
+    pub fn parse(input: &Input) -> Result<Self, input::Error> {
+        let mut parser = Parser::new(input);
+
+        let (number, l, start) = parser.line("`Begin in state <name>.`", begin)?;
+        let start = parser.reference(number, l, start);
+        let (_, _, steps) =
+            parser.line("`Perform a diagnostic checksum after <n> steps.`", checksum)?;
+
+        while !parser.is_done() {
+            parser.state()?;
+        }
+
+        parser.finish(start, steps)
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn states(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, state: usize) -> &str {
+        &self.names[state]
+    }
+
+    pub fn action(&self, state: usize, value: bool) -> Action {
+        self.actions[state * 2 + value as usize]
+    }
+}
+
+// A bit per slot, kept in whole words that double in size whenever the cursor walks off
+// either end, so a long run only ever touches a few megabytes at most.
+struct Tape {
+    words: Vec<u64>,
+    origin: usize,
+    cursor: usize,
+}
+
+impl Tape {
+    fn new() -> Self {
+        Tape {
+            words: vec![0; 4],
+            origin: 128,
+            cursor: 128,
+        }
+    }
+
+    fn read(&self) -> bool {
+        (self.words[self.cursor / 64] >> (self.cursor % 64)) & 1 == 1
+    }
+
+    fn write(&mut self, value: bool) {
+        let mask = 1 << (self.cursor % 64);
+
+        if value {
+            self.words[self.cursor / 64] |= mask;
+        } else {
+            self.words[self.cursor / 64] &= !mask;
+        }
+    }
+
+    fn move_left(&mut self) {
+        if self.cursor == 0 {
+            let len = self.words.len();
+            let mut words = vec![0; len * 2];
+
+            words[len..].copy_from_slice(&self.words);
+
+            self.words = words;
+            self.origin += len * 64;
+            self.cursor += len * 64;
+        }
+
+        self.cursor -= 1;
+    }
+
+    fn move_right(&mut self) {
+        self.cursor += 1;
+
+        if self.cursor == self.words.len() * 64 {
+            let len = self.words.len();
+            self.words.resize(len * 2, 0);
+        }
+    }
+
+    fn get(&self, position: isize) -> bool {
+        let bit = self.origin as isize + position;
+
+        bit >= 0
+            && (bit as usize) < self.words.len() * 64
+            && (self.words[bit as usize / 64] >> (bit as usize % 64)) & 1 == 1
+    }
+
+    fn ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+pub struct Machine<'a> {
+    blueprint: &'a Blueprint,
+    tape: Tape,
+    state: usize,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(blueprint: &'a Blueprint) -> Self {
+        Machine {
+            blueprint,
+            tape: Tape::new(),
+            state: blueprint.start,
+        }
+    }
+
+    pub fn state(&self) -> &str {
+        self.blueprint.name(self.state)
+    }
+
+    pub fn cursor(&self) -> isize {
+        self.tape.cursor as isize - self.tape.origin as isize
+    }
+
+    pub fn value(&self, position: isize) -> bool {
+        self.tape.get(position)
+    }
+
+    pub fn run(&mut self, steps: u64) {
+        let actions = &self.blueprint.actions;
+
+        for _ in 0..steps {
+            let action = actions[self.state * 2 + self.tape.read() as usize];
+
+            self.tape.write(action.write);
+
+            match action.movement {
+                Move::Left => self.tape.move_left(),
+                Move::Right => self.tape.move_right(),
+            }
+
+            self.state = action.next;
+        }
+    }
+
+    pub fn checksum(&self) -> usize {
+        self.tape.ones()
+    }
+}
+
+pub fn diagnostic_checksum(blueprint: &Blueprint) -> usize {
+    let mut machine = Machine::new(blueprint);
+
+    machine.run(blueprint.steps());
+    machine.checksum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Begin in state A.
+Perform a diagnostic checksum after 6 steps.
+
+In state A:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state B.
+  If the current value is 1:
+    - Write the value 0.
+    - Move one slot to the left.
+    - Continue with state B.
+
+In state B:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the left.
+    - Continue with state A.
+  If the current value is 1:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state A.
+";
+
+    fn parse(contents: &str) -> Result<Blueprint, input::Error> {
+        Blueprint::parse(&Input::embedded("input.txt", contents))
+    }
+
+    #[test]
+    fn parse_example() {
+        let blueprint = parse(EXAMPLE).unwrap();
+
+        assert_eq!("A", blueprint.name(blueprint.start()));
+        assert_eq!(6, blueprint.steps());
+        assert_eq!(2, blueprint.states());
+        assert_eq!(
+            Action {
+                write: false,
+                movement: Move::Left,
+                next: 1,
+            },
+            blueprint.action(0, true)
+        );
+    }
+
+    #[test]
+    fn run_example() {
+        let blueprint = parse(EXAMPLE).unwrap();
+        let mut machine = Machine::new(&blueprint);
+
+        machine.run(6);
+
+        assert_eq!("A", machine.state());
+        assert_eq!(0, machine.cursor());
+        assert_eq!(
+            vec![true, true, false, true],
+            (-2..2).map(|p| machine.value(p)).collect::<Vec<_>>()
+        );
+        assert_eq!(3, diagnostic_checksum(&blueprint));
+    }
+
+    #[test]
+    fn grow_tape_in_both_directions() {
+        let mut tape = Tape::new();
+
+        for _ in 0..1000 {
+            tape.write(true);
+            tape.move_left();
+        }
+
+        for _ in 0..3000 {
+            tape.move_right();
+        }
+
+        tape.write(true);
+
+        assert_eq!(1001, tape.ones());
+        assert!(tape.get(-999) && !tape.get(-1000) && tape.get(2000));
+    }
+
+    #[test]
+    fn run_for_millions_of_steps() {
+        let blueprint = parse(
+            "Begin in state A.\nPerform a diagnostic checksum after 10000000 steps.\n\
+             In state A:\nIf the current value is 0:\n- Write the value 1.\n\
+             - Move one slot to the right.\n- Continue with state A.\n\
+             If the current value is 1:\n- Write the value 0.\n\
+             - Move one slot to the left.\n- Continue with state A.\n",
+        )
+        .unwrap();
+
+        assert_eq!(10_000_000, diagnostic_checksum(&blueprint));
+    }
+
+    #[test]
+    fn report_unexpected_statement() {
+        let error = parse(&EXAMPLE.replacen("right.", "up.", 1))
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            error.starts_with("error: expected `left` or `right`, found `up`\n --> input.txt:7:28")
+        );
+    }
+
+    #[test]
+    fn report_undefined_state() {
+        let error = parse(&EXAMPLE.replace("Continue with state A.", "Continue with state C."))
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            error.starts_with("error: expected a defined state, found `C`\n  --> input.txt:18:27")
+        );
+    }
+
+    #[test]
+    fn report_missing_branch() {
+        let truncated = EXAMPLE.lines().take(18).collect::<Vec<_>>().join("\n");
+        let error = parse(&truncated).unwrap_err().to_string();
+
+        assert!(
+            error.starts_with("error: expected `If the current value is 1:`, found end of input")
+        );
+    }
+
+    #[test]
+    fn report_duplicate_state() {
+        let error = parse(&EXAMPLE.replace("In state B:", "In state A:"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.starts_with("error: expected a state that is not defined yet, found `A`"));
+    }
+}
@@ -0,0 +1,12 @@
+extern crate day25;
+extern crate input;
+
+use day25::*;
+use input::Input;
+
+fn main() {
+    let input = Input::from_path("input.txt").unwrap_or_else(|e| e.exit());
+    let blueprint = Blueprint::parse(&input).unwrap_or_else(|e| e.exit());
+
+    println!("Diagnostic checksum: {}", diagnostic_checksum(&blueprint));
+}
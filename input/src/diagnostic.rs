@@ -0,0 +1,255 @@
+use std::error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    input: String,
+    span: Range<usize>,
+    expected: String,
+    found: Option<&'static str>,
+    line: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new(input: &str, span: Range<usize>, expected: &str) -> Self {
+        ParseError {
+            input: input.to_owned(),
+            span,
+            expected: expected.to_owned(),
+            found: None,
+            line: None,
+        }
+    }
+
+    pub fn at_end(input: &str, expected: &str) -> Self {
+        ParseError::new(input, input.len()..input.len(), expected)
+    }
+
+    // Describes what was found instead of quoting the span, for spans that are empty without
+    // being at the end of the input.
+    pub fn found_instead(mut self, description: &'static str) -> Self {
+        self.found = Some(description);
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn within(mut self, input: &str, offset: usize) -> Self {
+        self.input = input.to_owned();
+        self.span = (self.span.start + offset)..(self.span.end + offset);
+        self
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn found(&self) -> &str {
+        &self.input[self.span.clone()]
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.input[..self.span.start].chars().count() + 1
+    }
+
+    pub fn message(&self) -> String {
+        if let Some(found) = self.found {
+            format!("expected {}, found {}", self.expected, found)
+        } else if self.span.start == self.span.end {
+            format!("expected {}, found end of input", self.expected)
+        } else {
+            format!("expected {}, found `{}`", self.expected, self.found())
+        }
+    }
+
+    pub fn render(&self, name: Option<&str>) -> String {
+        let line = self.line.unwrap_or(1);
+        let gutter = " ".repeat(line.to_string().len());
+        let carets = "^".repeat(self.found().chars().count().max(1));
+
+        let location = match name {
+            Some(name) => format!("{}:{}:{}", name, line, self.column()),
+            None => format!("{}:{}", line, self.column()),
+        };
+
+        format!(
+            "error: {}\n{}--> {}\n{} |\n{} | {}\n{} | {}{} expected {}",
+            self.message(),
+            gutter,
+            location,
+            gutter,
+            line,
+            self.input,
+            gutter,
+            " ".repeat(self.column() - 1),
+            carets,
+            self.expected
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(None))
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token<'a> {
+    input: &'a str,
+    pub text: &'a str,
+    pub span: Range<usize>,
+}
+
+impl<'a> Token<'a> {
+    pub fn error(&self, expected: &str) -> ParseError {
+        ParseError::new(self.input, self.span.clone(), expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.error(expected))
+    }
+}
+
+pub struct Tokens<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Tokens { input, position: 0 }
+    }
+
+    pub fn expect(&mut self, expected: &str) -> Result<Token<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| ParseError::at_end(self.input, expected))
+    }
+
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        match self.next() {
+            Some(token) => Err(token.error("end of input")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let rest = &self.input[self.position..];
+        let start = self.position + rest.len() - rest.trim_start().len();
+        let rest = &self.input[start..];
+        let end = start + rest.find(char::is_whitespace).unwrap_or(rest.len());
+
+        if start == end {
+            self.position = end;
+            return None;
+        }
+
+        self.position = end;
+
+        Some(Token {
+            input: self.input,
+            text: &self.input[start..end],
+            span: start..end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_into_tokens() {
+        let tokens = Tokens::new("  b inc  5 ")
+            .map(|t| (t.text, t.span))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![("b", 2..3), ("inc", 4..7), ("5", 9..10)], tokens);
+    }
+
+    #[test]
+    fn missing_token() {
+        let mut tokens = Tokens::new("abcd");
+
+        tokens.expect("a name").unwrap();
+
+        let error = tokens.expect("a weight").unwrap_err();
+
+        assert_eq!(4..4, error.span());
+        assert_eq!("expected a weight, found end of input", error.message());
+    }
+
+    #[test]
+    fn describe_what_was_found() {
+        let error = ParseError::new("5,,1", 2..2, "a number").found_instead("an empty field");
+
+        assert_eq!("expected a number, found an empty field", error.message());
+    }
+
+    #[test]
+    fn invalid_token() {
+        let token = Tokens::new("b inc x").nth(2).unwrap();
+        let error = token.parse::<i32>("a number").unwrap_err();
+
+        assert_eq!("x", error.found());
+        assert_eq!(7, error.column());
+        assert_eq!("expected a number, found `x`", error.message());
+    }
+
+    #[test]
+    fn render_with_carets() {
+        let error = ParseError::new("b inc 5x if a > 1", 6..8, "a number").at_line(12);
+
+        assert_eq!(
+            [
+                "error: expected a number, found `5x`",
+                "  --> input.txt:12:7",
+                "   |",
+                "12 | b inc 5x if a > 1",
+                "   |       ^^ expected a number",
+            ]
+            .join("\n"),
+            error.render(Some("input.txt"))
+        );
+    }
+
+    #[test]
+    fn render_within_larger_input() {
+        let error = ParseError::new("nw2", 0..3, "a direction").within("ne,s,nw2", 5);
+
+        assert_eq!(
+            [
+                "error: expected a direction, found `nw2`",
+                " --> 1:6",
+                "  |",
+                "1 | ne,s,nw2",
+                "  |      ^^^ expected a direction",
+            ]
+            .join("\n"),
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,391 @@
+mod diagnostic;
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+
+pub use diagnostic::{ParseError, Token, Tokens};
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        name: String,
+        error: io::Error,
+    },
+    Parse {
+        name: String,
+        line: usize,
+        token: String,
+    },
+    Syntax {
+        name: String,
+        error: ParseError,
+    },
+}
+
+impl Error {
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self);
+        process::exit(1);
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io {
+                ref name,
+                ref error,
+            } => write!(f, "{}: {}", name, error),
+            Error::Parse {
+                ref name,
+                line,
+                ref token,
+            } => write!(f, "{}:{}: invalid value `{}`", name, line, token),
+            Error::Syntax {
+                ref name,
+                ref error,
+            } => write!(f, "{}", error.render(Some(name))),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io { ref error, .. } => Some(error),
+            Error::Parse { .. } => None,
+            Error::Syntax { ref error, .. } => Some(error),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Strict,
+    Lenient,
+}
+
+pub struct Input {
+    name: String,
+    contents: String,
+    mode: Mode,
+}
+
+impl Input {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let name = path.as_ref().display().to_string();
+
+        match File::open(path) {
+            Ok(file) => Input::from_reader(&name, file),
+            Err(error) => Err(Error::Io { name, error }),
+        }
+    }
+
+    pub fn from_stdin() -> Result<Self, Error> {
+        Input::from_reader("<stdin>", io::stdin())
+    }
+
+    pub fn from_reader<R: Read>(name: &str, mut reader: R) -> Result<Self, Error> {
+        let mut contents = String::new();
+
+        reader
+            .read_to_string(&mut contents)
+            .map_err(|error| Error::Io {
+                name: name.to_owned(),
+                error,
+            })?;
+
+        Ok(Input::embedded(name, &contents))
+    }
+
+    pub fn embedded(name: &str, contents: &str) -> Self {
+        Input {
+            name: name.to_owned(),
+            contents: contents.to_owned(),
+            mode: Mode::Strict,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    pub fn trimmed(&self) -> &str {
+        self.contents.trim()
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.contents
+            .lines()
+            .enumerate()
+            .map(|(index, l)| (index + 1, l))
+    }
+
+    // Lines are returned as they are in the file, so that spans into them point at the
+    // columns the file actually has.
+    fn non_blank_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines().filter(|&(_, l)| !l.trim().is_empty())
+    }
+
+    pub fn invalid(&self, line: usize, token: &str) -> Error {
+        Error::Parse {
+            name: self.name.clone(),
+            line,
+            token: token.to_owned(),
+        }
+    }
+
+    pub fn parse<T: FromStr + Default>(&self, line: usize, token: &str) -> Result<T, Error> {
+        match token.parse() {
+            Ok(value) => Ok(value),
+            Err(_) if self.mode == Mode::Lenient => Ok(T::default()),
+            Err(_) => Err(self.invalid(line, token)),
+        }
+    }
+
+    pub fn value<T: FromStr>(&self) -> Result<T, Error> {
+        let line = self.non_blank_lines().next().map(|(number, _)| number);
+
+        self.trimmed()
+            .parse()
+            .map_err(|_| self.invalid(line.unwrap_or(1), self.trimmed()))
+    }
+
+    pub fn values<T: FromStr + Default>(&self) -> Result<Vec<T>, Error> {
+        self.non_blank_lines()
+            .map(|(number, l)| self.parse(number, l.trim()))
+            .collect()
+    }
+
+    pub fn tokens<T: FromStr + Default>(&self) -> Result<Vec<T>, Error> {
+        let mut tokens = Vec::new();
+
+        for row in self.rows()? {
+            tokens.extend(row);
+        }
+
+        Ok(tokens)
+    }
+
+    pub fn rows<T: FromStr + Default>(&self) -> Result<Vec<Vec<T>>, Error> {
+        self.non_blank_lines()
+            .map(|(number, l)| {
+                l.split_whitespace()
+                    .map(|token| self.parse(number, token))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn syntax(&self, line: usize, error: ParseError) -> Error {
+        Error::Syntax {
+            name: self.name.clone(),
+            error: error.at_line(line),
+        }
+    }
+
+    pub fn fields<T: FromStr<Err = ParseError>>(&self, separator: char) -> Result<Vec<T>, Error> {
+        let mut fields = Vec::new();
+
+        for (number, l) in self.non_blank_lines() {
+            let mut offset = 0;
+
+            for field in l.split(separator) {
+                let trimmed = field.trim();
+                let start = offset + field.len() - field.trim_start().len();
+
+                fields.push(
+                    trimmed
+                        .parse()
+                        .map_err(|e: ParseError| self.syntax(number, e.within(l, start)))?,
+                );
+
+                offset += field.len() + separator.len_utf8();
+            }
+        }
+
+        Ok(fields)
+    }
+
+    pub fn records<T: FromStr<Err = ParseError>>(&self) -> Result<Vec<T>, Error> {
+        self.non_blank_lines()
+            .map(|(number, l)| {
+                let indent = l.len() - l.trim_start().len();
+
+                l.trim()
+                    .parse()
+                    .map_err(|e: ParseError| self.syntax(number, e.within(l, indent)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Digit(u32);
+
+    impl FromStr for Digit {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.parse() {
+                Ok(d) if d < 10 => Ok(Digit(d)),
+                _ => Err(ParseError::new(s, 0..s.len(), "a digit")),
+            }
+        }
+    }
+
+    #[test]
+    fn read_from_reader() {
+        let input = Input::from_reader("test", "1 2\n3\n".as_bytes()).unwrap();
+
+        assert_eq!("test", input.name());
+        assert_eq!("1 2\n3\n", input.contents());
+    }
+
+    #[test]
+    fn missing_file() {
+        match Input::from_path("does/not/exist.txt") {
+            Err(Error::Io { name, .. }) => assert_eq!("does/not/exist.txt", name),
+            _ => panic!("expected io error"),
+        }
+    }
+
+    #[test]
+    fn parse_single_value() {
+        let input = Input::embedded("test", "\n361527\n");
+
+        assert_eq!(361527, input.value::<u32>().unwrap());
+    }
+
+    #[test]
+    fn parse_values() {
+        let input = Input::embedded("test", "0\n3\n\n-3\n");
+
+        assert_eq!(vec![0, 3, -3], input.values::<i32>().unwrap());
+    }
+
+    #[test]
+    fn parse_rows() {
+        let input = Input::embedded("test", "5 1 9 5\n7\t5 3\n");
+
+        assert_eq!(
+            vec![vec![5, 1, 9, 5], vec![7, 5, 3]],
+            input.rows::<u32>().unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_tokens() {
+        let input = Input::embedded("test", "0 2\t7 0\n");
+
+        assert_eq!(vec![0, 2, 7, 0], input.tokens::<u32>().unwrap());
+    }
+
+    #[test]
+    fn parse_fields() {
+        let input = Input::embedded("test", "1,2, 3\n");
+
+        assert_eq!(
+            vec![Digit(1), Digit(2), Digit(3)],
+            input.fields(',').unwrap()
+        );
+    }
+
+    #[test]
+    fn report_malformed_token() {
+        let input = Input::embedded("input.txt", "5 1 9 5\n7 x 3\n");
+
+        assert_eq!(
+            "input.txt:2: invalid value `x`",
+            input.rows::<u32>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn report_malformed_record() {
+        let input = Input::embedded("input.txt", "1\n\nabc\n");
+
+        match input.records::<Digit>() {
+            Err(Error::Syntax { name, error }) => {
+                assert_eq!("input.txt", name);
+                assert_eq!(Some(3), error.line());
+                assert_eq!("abc", error.found());
+            }
+            _ => panic!("expected syntax error"),
+        }
+    }
+
+    #[test]
+    fn report_malformed_field() {
+        let input = Input::embedded("input.txt", "1,2\n3, 42\n");
+
+        assert_eq!(
+            [
+                "error: expected a digit, found `42`",
+                " --> input.txt:2:4",
+                "  |",
+                "2 | 3, 42",
+                "  |    ^^ expected a digit",
+            ]
+            .join("\n"),
+            input.fields::<Digit>(',').unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn report_columns_of_indented_lines() {
+        let input = Input::embedded("input.txt", "1\n    x \n");
+
+        match input.records::<Digit>() {
+            Err(Error::Syntax { error, .. }) => {
+                assert_eq!(5, error.column());
+                assert_eq!("    x ", error.input());
+                assert_eq!("x", error.found());
+            }
+            _ => panic!("expected syntax error"),
+        }
+
+        let input = Input::embedded("input.txt", "1,2\n  3, 42\n");
+
+        assert_eq!(
+            [
+                "error: expected a digit, found `42`",
+                " --> input.txt:2:6",
+                "  |",
+                "2 |   3, 42",
+                "  |      ^^ expected a digit",
+            ]
+            .join("\n"),
+            input.fields::<Digit>(',').unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn lenient_mode_coerces_to_default() {
+        let input = Input::embedded("test", "7 x 3\n").with_mode(Mode::Lenient);
+
+        assert_eq!(vec![vec![7, 0, 3]], input.rows::<u32>().unwrap());
+    }
+}
@@ -0,0 +1,368 @@
+extern crate input;
+
+use std::hash::{BuildHasher, Hasher};
+
+use input::ParseError;
+
+pub const LIST_SIZE: usize = 256;
+pub const ROUNDS: usize = 64;
+pub const SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+pub const DENSE_SIZE: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnotHash {
+    size: usize,
+    rounds: usize,
+    suffix: Vec<u8>,
+}
+
+impl Default for KnotHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnotHash {
+    pub fn new() -> Self {
+        KnotHash {
+            size: LIST_SIZE,
+            rounds: ROUNDS,
+            suffix: SUFFIX.to_vec(),
+        }
+    }
+
+    pub fn with_size(mut self, size: usize) -> Self {
+        assert!(
+            size > 0 && size <= LIST_SIZE,
+            "list size must be between 1 and 256"
+        );
+
+        self.size = size;
+        self
+    }
+
+    pub fn with_rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn with_suffix(mut self, suffix: &[u8]) -> Self {
+        self.suffix = suffix.to_vec();
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn suffix(&self) -> &[u8] {
+        &self.suffix
+    }
+
+    fn knot(&self, lengths: &[usize]) -> Vec<u8> {
+        let mut list = (0..self.size).map(|i| i as u8).collect::<Vec<_>>();
+
+        let mut position = 0;
+        let mut skip_size = 0;
+
+        for _ in 0..self.rounds {
+            for &length in lengths {
+                for i in 0..(length / 2) {
+                    list.swap(
+                        (position + i) % self.size,
+                        (position + (length - 1 - i)) % self.size,
+                    );
+                }
+
+                position = (position + length + skip_size) % self.size;
+                skip_size += 1;
+            }
+        }
+
+        list
+    }
+
+    pub fn sparse(&self, input: &[u8]) -> Vec<u8> {
+        let lengths = input
+            .iter()
+            .chain(self.suffix.iter())
+            .map(|&b| b as usize)
+            .collect::<Vec<_>>();
+
+        self.knot(&lengths)
+    }
+
+    pub fn dense(&self, input: &[u8]) -> [u8; DENSE_SIZE] {
+        assert!(
+            self.size.is_multiple_of(DENSE_SIZE),
+            "list size must be a multiple of 16 for a dense hash"
+        );
+
+        let mut dense = [0; DENSE_SIZE];
+
+        for (d, block) in dense
+            .iter_mut()
+            .zip(self.sparse(input).chunks(self.size / DENSE_SIZE))
+        {
+            *d = block.iter().fold(0, |acc, &x| acc ^ x);
+        }
+
+        dense
+    }
+
+    pub fn hex(&self, input: &[u8]) -> String {
+        to_hex(&self.dense(input))
+    }
+
+    pub fn bits(&self, input: &[u8]) -> String {
+        to_bits(&self.dense(input))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Round {
+    pub list: Vec<u8>,
+    pub product: u32,
+}
+
+pub fn parse_lengths(s: &str, size: usize) -> Result<Vec<usize>, ParseError> {
+    let mut lengths = Vec::new();
+
+    if s.trim().is_empty() {
+        return Ok(lengths);
+    }
+
+    let mut offset = 0;
+
+    for field in s.split(',') {
+        let start = offset + field.len() - field.trim_start().len();
+        let span = start..(start + field.trim().len());
+        let expected = format!("a length between 0 and {}", size);
+
+        match field.trim().parse() {
+            Ok(length) if length <= size => lengths.push(length),
+            _ => return Err(ParseError::new(s, span, &expected)),
+        }
+
+        offset += field.len() + 1;
+    }
+
+    Ok(lengths)
+}
+
+pub fn single_round(size: usize, lengths: &str) -> Result<Round, ParseError> {
+    assert!(size >= 2, "list size must be at least 2");
+
+    let lengths = parse_lengths(lengths, size)?;
+    let list = KnotHash::new()
+        .with_size(size)
+        .with_rounds(1)
+        .knot(&lengths);
+    let product = u32::from(list[0]) * u32::from(list[1]);
+
+    Ok(Round { list, product })
+}
+
+// Every round runs over the whole input, so the hasher can only buffer what it is given
+// and does the actual work when the hash is requested.
+#[derive(Clone, Debug, Default)]
+pub struct KnotHasher {
+    knot: KnotHash,
+    buffer: Vec<u8>,
+}
+
+impl KnotHasher {
+    pub fn new() -> Self {
+        Self::with_knot(KnotHash::new())
+    }
+
+    pub fn with_knot(knot: KnotHash) -> Self {
+        KnotHasher {
+            knot,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    pub fn finalize(self) -> [u8; DENSE_SIZE] {
+        self.knot.dense(&self.buffer)
+    }
+}
+
+impl Hasher for KnotHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let dense = self.knot.dense(&self.buffer);
+
+        dense[..8]
+            .iter()
+            .fold(0, |acc, &b| (acc << 8) | u64::from(b))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildKnotHasher;
+
+impl BuildHasher for BuildKnotHasher {
+    type Hasher = KnotHasher;
+
+    fn build_hasher(&self) -> KnotHasher {
+        KnotHasher::new()
+    }
+}
+
+pub fn hash(input: &[u8]) -> [u8; DENSE_SIZE] {
+    KnotHash::new().dense(input)
+}
+
+pub fn to_hex(hash: &[u8]) -> String {
+    hash.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn to_bits(hash: &[u8]) -> String {
+    hash.iter().map(|b| format!("{:08b}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn hash_empty_string() {
+        assert_eq!("a2582a3a0e66e6e86e3812dcb672a272", to_hex(&hash(b"")));
+    }
+
+    #[test]
+    fn hash_test_string() {
+        assert_eq!(
+            "33efeb34ea91902bb2f59c9920caa6cd",
+            KnotHash::new().hex(b"AoC 2017")
+        );
+    }
+
+    #[test]
+    fn dense_hash_bytes() {
+        assert_eq!(
+            [
+                0x3e, 0xfb, 0xe7, 0x8a, 0x8d, 0x82, 0xf2, 0x99, 0x79, 0x03, 0x1a, 0x4a, 0xa0, 0xb1,
+                0x6a, 0x9d
+            ],
+            hash(b"1,2,3")
+        );
+    }
+
+    #[test]
+    fn render_bits() {
+        assert_eq!("1010000011111111", to_bits(&[0xa0, 0xff]));
+        assert_eq!(128, KnotHash::new().bits(b"flqrgnkx-0").len());
+        assert!(KnotHash::new().bits(b"flqrgnkx-0").starts_with("11010100"));
+    }
+
+    #[test]
+    fn sparse_hash_without_suffix() {
+        let knot = KnotHash::new().with_size(5).with_rounds(1).with_suffix(&[]);
+
+        assert_eq!(vec![3, 4, 2, 1, 0], knot.sparse(&[3, 4, 1, 5]));
+    }
+
+    #[test]
+    fn single_round_example() {
+        assert_eq!(
+            Round {
+                list: vec![3, 4, 2, 1, 0],
+                product: 12,
+            },
+            single_round(5, "3, 4, 1, 5").unwrap()
+        );
+    }
+
+    #[test]
+    fn single_round_accepts_full_length() {
+        let round = single_round(256, "256").unwrap();
+
+        assert_eq!(255, round.list[0]);
+        assert_eq!(255 * 254, round.product);
+    }
+
+    #[test]
+    fn parse_empty_lengths() {
+        assert_eq!(Vec::<usize>::new(), parse_lengths("\n", 256).unwrap());
+    }
+
+    #[test]
+    fn reject_length_longer_than_list() {
+        let error = single_round(5, "3,6,1").unwrap_err();
+
+        assert_eq!(2..3, error.span());
+        assert_eq!(
+            "expected a length between 0 and 5, found `6`",
+            error.message()
+        );
+    }
+
+    #[test]
+    fn reject_malformed_length() {
+        let error = parse_lengths("3, x4", 256).unwrap_err();
+
+        assert_eq!("x4", error.found());
+    }
+
+    #[test]
+    fn hash_incrementally() {
+        let mut hasher = KnotHasher::new();
+
+        hasher.update(b"AoC");
+        hasher.update(b"");
+        hasher.update(b" 2017");
+
+        assert_eq!(hash(b"AoC 2017"), hasher.finalize());
+    }
+
+    #[test]
+    fn hash_with_custom_knot() {
+        let knot = KnotHash::new().with_rounds(1);
+        let mut hasher = KnotHasher::with_knot(knot.clone());
+
+        hasher.update(b"1,2,3");
+
+        assert_eq!(knot.dense(b"1,2,3"), hasher.finalize());
+    }
+
+    #[test]
+    fn finish_uses_leading_bytes() {
+        let mut hasher = KnotHasher::new();
+
+        hasher.write(b"1,2,3");
+
+        assert_eq!(0x3efb_e78a_8d82_f299, hasher.finish());
+    }
+
+    #[test]
+    fn use_as_hash_map_hasher() {
+        let mut map = HashMap::with_hasher(BuildKnotHasher);
+
+        map.insert("flqrgnkx", 8108);
+        map.insert("AoC 2017", 2017);
+
+        assert_eq!(Some(&8108), map.get("flqrgnkx"));
+        assert_eq!(None, map.get("aoc 2017"));
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of 16")]
+    fn dense_hash_needs_full_blocks() {
+        KnotHash::new().with_size(5).dense(b"");
+    }
+}